@@ -1,5 +1,6 @@
 use candle_auction::msg::*;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
+use cosmwasm_std::Uint128;
 
 use std::env::current_dir;
 use std::fs::create_dir_all;
@@ -13,8 +14,73 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(Cw721HookMsg), &out_dir);
 
     export_schema_with_title(&mut schema_for!(response::Config), &out_dir, "Config");
 
-    export_schema_with_title(&mut schema_for!(response::Auction), &out_dir, "Auction");
+    export_schema_with_title(
+        &mut schema_for!(Option<response::Auction>),
+        &out_dir,
+        "Auction",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Option<Vec<response::Auction>>),
+        &out_dir,
+        "AuctionList",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<response::Auction>),
+        &out_dir,
+        "AuctionsBySeller",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<response::Auction>),
+        &out_dir,
+        "AuctionsByCollection",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<response::Auction>),
+        &out_dir,
+        "AuctionsByPayment",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<response::AuctionBid>),
+        &out_dir,
+        "Bids",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<response::BidderAuction>),
+        &out_dir,
+        "BidsByBidder",
+    );
+    export_schema_with_title(&mut schema_for!(Vec<String>), &out_dir, "Sellers");
+    export_schema_with_title(&mut schema_for!(response::Roles), &out_dir, "Roles");
+    export_schema_with_title(
+        &mut schema_for!(Vec<(PaymentAsset, Uint128)>),
+        &out_dir,
+        "Fees",
+    );
+    export_schema_with_title(
+        &mut schema_for!(response::PauseState),
+        &out_dir,
+        "PauseState",
+    );
+    export_schema_with_title(&mut schema_for!(response::Stats), &out_dir, "Stats");
+    export_schema_with_title(
+        &mut schema_for!(response::SellerStats),
+        &out_dir,
+        "SellerStats",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<response::Collection>),
+        &out_dir,
+        "Collections",
+    );
+    export_schema_with_title(
+        &mut schema_for!(Vec<PaymentAsset>),
+        &out_dir,
+        "PaymentAssets",
+    );
 }
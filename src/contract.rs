@@ -1,12 +1,18 @@
 use crate::error::ContractError;
 use crate::handler::*;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::state::{Config, ContractVersion, CONFIG};
+use crate::migration::migrate_state;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg};
+use crate::state::{
+    default_max_royalty, CollectionInfo, Config, CreationPolicy, COLLECTIONS, CONFIG,
+    PAYMENT_ALLOWLIST,
+};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError,
+    StdResult, SubMsgResult,
 };
 use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
 
 const CONTRACT_NAME: &str = "crates.io:candle_auction";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -20,25 +26,37 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     let owner = deps.api.addr_canonicalize(info.sender.as_str())?;
     let oracle_contract = deps.api.addr_canonicalize(&msg.oracle_contract)?;
+    let price_oracle = msg
+        .price_oracle
+        .map(|addr| deps.api.addr_canonicalize(&addr))
+        .transpose()?;
+
+    let config = Config {
+        auction_num: 0,
+        min_auction_duration: msg.min_auction_duration,
+        max_auction_duration: msg.max_auction_duration,
+        enable_auction: msg.enable_auction,
+        fee_rate: msg.fee_rate,
+        default_denom: msg.default_denom,
+        owner,
+        oracle_contract,
+        price_oracle,
+        restrict_payments: msg.payment_allowlist.is_some(),
+        max_royalty: msg.max_royalty.unwrap_or_else(default_max_royalty),
+        creation_policy: CreationPolicy::Open,
+        keeper_reward: 0,
+    };
+    config.validate()?;
+    CONFIG.save(deps.storage, &config)?;
 
-    CONFIG.save(
-        deps.storage,
-        &Config {
-            auction_num: 0,
-            min_auction_duration: msg.min_auction_duration,
-            max_auction_duration: msg.max_auction_duration,
-            enable_auction: msg.enable_auction,
-            fee_rate: msg.fee_rate,
-            default_denom: msg.default_denom,
-            support_contract: msg.support_contract,
-            version: ContractVersion {
-                contract: CONTRACT_NAME.to_string(),
-                version: CONTRACT_VERSION.to_string(),
-            },
-            owner,
-            oracle_contract,
-        },
-    )?;
+    for contract in msg.support_contract {
+        let contract = deps.api.addr_validate(&contract)?;
+        COLLECTIONS.save(deps.storage, contract.as_str(), &CollectionInfo::default())?;
+    }
+
+    for asset in msg.payment_allowlist.unwrap_or_default() {
+        PAYMENT_ALLOWLIST.save(deps.storage, &asset.payment, &asset.payment_type)?;
+    }
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -62,6 +80,11 @@ pub fn execute(
             payment_type,
             payment,
             min_price,
+            mode,
+            accepted_payments,
+            lots_expected,
+            eligibility,
+            metadata,
         } => execute::auction(
             deps,
             env,
@@ -72,18 +95,123 @@ pub fn execute(
             payment_type,
             payment,
             min_price,
+            mode,
+            accepted_payments,
+            lots_expected,
+            eligibility,
+            metadata,
         ),
-        ExecuteMsg::Receive(msg) => execute::receive_cw20(deps, env, info, msg),
-        ExecuteMsg::ReceiveNft(msg) => execute::receive_cw721(deps, env, info, msg),
-        ExecuteMsg::WinnerClaim { auction_id, winner } => {
-            execute::winner_claim(deps, env, info, auction_id, winner)
+        ExecuteMsg::Receive(ReceiveMsg::Cw20(msg)) => execute::receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Receive(ReceiveMsg::Cw1155(msg)) => {
+            execute::receive_cw1155(deps, env, info, msg)
         }
+        ExecuteMsg::ReceiveNft(msg) => execute::receive_cw721(deps, env, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute::batch_receive_cw1155(deps, env, info, msg),
+        ExecuteMsg::UpdateAuction {
+            auction_id,
+            name,
+            start_timestamp,
+            duration,
+            min_price,
+            metadata,
+        } => execute::update_auction(
+            deps,
+            env,
+            info,
+            auction_id,
+            name,
+            start_timestamp,
+            duration,
+            min_price,
+            metadata,
+        ),
         ExecuteMsg::BlowCandle { auction_id } => execute::blow_candle(deps, env, auction_id),
         ExecuteMsg::FlowRefund { auction_id } => execute::auction_flow(deps, env, auction_id),
-        ExecuteMsg::BidForDenom { bidder, auction_id } => {
-            execute::bid_for_denom(deps, env, info, bidder, auction_id)
+        ExecuteMsg::ClaimRefund { auction_id } => execute::claim_refund(deps, env, auction_id),
+        ExecuteMsg::SettleBatch { start_after, limit } => {
+            execute::settle_batch(deps, env, info, start_after, limit)
         }
-        _ => Err(ContractError::InvalidName {}),
+        ExecuteMsg::KeeperSettle { auction_id, keeper } => {
+            execute::keeper_settle(deps, env, info, auction_id, keeper)
+        }
+        ExecuteMsg::Relist {
+            auction_id,
+            start_timestamp,
+            duration,
+            min_price,
+        } => execute::relist(
+            deps,
+            env,
+            info,
+            auction_id,
+            start_timestamp,
+            duration,
+            min_price,
+        ),
+        ExecuteMsg::BidForDenom {
+            bidder,
+            auction_id,
+            proof,
+        } => execute::bid_for_denom(deps, env, info, bidder, auction_id, proof),
+        ExecuteMsg::UpdateConfig {
+            min_auction_duration,
+            max_auction_duration,
+            enable_auction,
+            fee_rate,
+            default_denom,
+            price_oracle,
+            restrict_payments,
+            max_royalty,
+            keeper_reward,
+        } => execute::update_config(
+            deps,
+            info,
+            min_auction_duration,
+            max_auction_duration,
+            enable_auction,
+            fee_rate,
+            default_denom,
+            price_oracle,
+            restrict_payments,
+            max_royalty,
+            keeper_reward,
+        ),
+        ExecuteMsg::GrantRole { address, role } => execute::grant_role(deps, info, address, role),
+        ExecuteMsg::RevokeRole { address, role } => execute::revoke_role(deps, info, address, role),
+        ExecuteMsg::UpdateCreationPolicy { policy } => {
+            execute::update_creation_policy(deps, info, policy)
+        }
+        ExecuteMsg::AddSellers { sellers } => execute::add_sellers(deps, info, sellers),
+        ExecuteMsg::RemoveSellers { sellers } => execute::remove_sellers(deps, info, sellers),
+        ExecuteMsg::UpdateFeeRate { fee_rate } => execute::update_fee_rate(deps, info, fee_rate),
+        ExecuteMsg::WithdrawFees {
+            payment,
+            amount,
+            recipient,
+        } => execute::withdraw_fees(deps, info, payment, amount, recipient),
+        ExecuteMsg::Pause { scopes, reason } => execute::pause(deps, env, info, scopes, reason),
+        ExecuteMsg::Unpause { scopes } => execute::unpause(deps, info, scopes),
+        ExecuteMsg::AddCollection {
+            contract,
+            royalty,
+            min_duration,
+        } => execute::add_collection(deps, info, contract, royalty, min_duration),
+        ExecuteMsg::RemoveCollection { contract } => {
+            execute::remove_collection(deps, info, contract)
+        }
+        ExecuteMsg::AddPaymentAsset { asset } => execute::add_payment_asset(deps, info, asset),
+        ExecuteMsg::RemovePaymentAsset { payment } => {
+            execute::remove_payment_asset(deps, info, payment)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        // only the failures of the `SettleBatch` sub-messages are replied
+        SubMsgResult::Err(err) => execute::settle_failed(msg.id, err),
+        SubMsgResult::Ok(_) => Ok(Response::new()),
     }
 }
 
@@ -91,29 +219,135 @@ pub fn execute(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query::config(deps)?),
-        QueryMsg::Auction { id } => to_binary(&query::auction(deps, id)?),
+        QueryMsg::Auction { id } => to_binary(&query::auction(deps, env, id)?),
         QueryMsg::AuctionList {
             status,
-            page,
+            settled,
+            start_after,
+            limit,
+        } => to_binary(&query::auction_list(
+            deps,
+            env,
+            status,
+            settled,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AuctionsBySeller {
+            seller,
+            start_after,
+            limit,
+        } => to_binary(&query::auctions_by_seller(
+            deps,
+            env,
+            seller,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AuctionsByCollection {
+            collection,
+            start_after,
+            limit,
+        } => to_binary(&query::auctions_by_collection(
+            deps,
+            env,
+            collection,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AuctionsByPayment {
+            payment,
+            start_after,
+            limit,
+        } => to_binary(&query::auctions_by_payment(
+            deps,
+            env,
+            payment,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Bids {
+            auction_id,
+            start_after,
+            limit,
+            order,
+        } => to_binary(&query::bids(deps, auction_id, start_after, limit, order)?),
+        QueryMsg::BidsByBidder {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&query::bids_by_bidder(
+            deps,
+            env,
+            address,
+            start_after,
             limit,
-        } => to_binary(&query::auction_list(deps, env, status, page, limit)?),
+        )?),
+        QueryMsg::Sellers { start_after, limit } => {
+            to_binary(&query::sellers(deps, start_after, limit)?)
+        }
+        QueryMsg::Roles { address } => to_binary(&query::roles(deps, address)?),
+        QueryMsg::Fees {} => to_binary(&query::fees(deps)?),
+        QueryMsg::PauseState {} => to_binary(&query::pause_state(deps)?),
+        QueryMsg::Stats {} => to_binary(&query::stats(deps)?),
+        QueryMsg::SellerStats { address } => to_binary(&query::seller_stats(deps, address)?),
+        QueryMsg::Collections { start_after, limit } => {
+            to_binary(&query::collections(deps, start_after, limit)?)
+        }
+        QueryMsg::PaymentAssets { start_after, limit } => {
+            to_binary(&query::payment_assets(deps, start_after, limit)?)
+        }
     }
 }
 
 #[entry_point]
-pub fn migrate(deps: DepsMut, _env: Env, _: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let ver = get_contract_version(deps.storage)?;
     // ensure we are migrating from an allowed contract
     if ver.contract != CONTRACT_NAME {
         return Err(StdError::generic_err("Can only upgrade from same type").into());
     }
 
-    if ver.version.gt(&CONTRACT_VERSION.to_string()) {
+    let parse = |version: &str| {
+        Version::parse(version)
+            .map_err(|_| StdError::generic_err(format!("Invalid version: {}", version)))
+    };
+    let stored = parse(&ver.version)?;
+    let current = parse(CONTRACT_VERSION)?;
+    if stored > current {
         return Err(StdError::generic_err("Cannot upgrade from a newer version").into());
     }
 
+    let steps = migrate_state(deps.branch(), &stored, &current)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    if let Some(min_auction_duration) = msg.min_auction_duration {
+        config.min_auction_duration = min_auction_duration;
+    }
+    if let Some(max_auction_duration) = msg.max_auction_duration {
+        config.max_auction_duration = max_auction_duration;
+    }
+    if let Some(enable_auction) = msg.enable_auction {
+        config.enable_auction = enable_auction;
+    }
+    if let Some(fee_rate) = msg.fee_rate {
+        config.fee_rate = fee_rate;
+    }
+    if let Some(default_denom) = msg.default_denom {
+        config.default_denom = default_denom;
+    }
+    if let Some(max_royalty) = msg.max_royalty {
+        config.max_royalty = max_royalty;
+    }
+    config.validate()?;
+    CONFIG.save(deps.storage, &config)?;
+
     // set the new version
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    Ok(Response::default())
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", ver.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("steps", steps.join(",")))
 }
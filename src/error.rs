@@ -55,6 +55,45 @@ pub enum ContractError {
     #[error("AuctionPriceTooLow")]
     AuctionPriceTooLow { min_price: u128, current: u128 },
 
+    #[error("InvalidPayment: {payment} is not a valid denom or cw20 contract")]
+    InvalidPayment { payment: String },
+
+    #[error("UnsupportedPayment: {payment} is not an allowed payment")]
+    UnsupportedPayment { payment: String },
+
+    #[error("UnexpectedLot: The lot was not declared by the auction")]
+    UnexpectedLot {},
+
+    #[error("LotsNotDeposited: The auction lots were not all deposited before the start")]
+    LotsNotDeposited {},
+
+    #[error("MissingRole: {sender} is not the owner nor a {role}")]
+    MissingRole { sender: String, role: String },
+
+    #[error("NotEligible: {bidder} is not eligible to bid on the auction")]
+    NotEligible { bidder: String },
+
+    #[error("SellerNotAllowed: {seller} is not allowed to create auctions")]
+    SellerNotAllowed { seller: String },
+
+    #[error("Paused: {scope} is paused: {reason}")]
+    Paused { scope: String, reason: String },
+
+    #[error("InvalidDuration: {duration} is not within [{min}, {max}]")]
+    InvalidDuration { duration: u64, min: u64, max: u64 },
+
+    #[error("AuctionLocked: The auction started or has bids")]
+    AuctionLocked {},
+
+    #[error("InvalidMetadata: {field} is longer than {max}")]
+    InvalidMetadata { field: String, max: usize },
+
+    #[error("AuctionNotPassed: The auction did not end without bids")]
+    AuctionNotPassed {},
+
+    #[error("AuctionAlreadySettled: The candle of the auction was already blown")]
+    AuctionAlreadySettled {},
+
     #[error("BadRequest")]
     BadRequest { msg: String },
 }
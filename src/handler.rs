@@ -1,19 +1,688 @@
 use crate::error::ContractError;
-use crate::msg::{response, Auction as AuctionMsg, RandQueryMsg};
+use crate::msg::{
+    response, Auction as AuctionMsg, Cw20HookMsg, Cw2981Query, Cw2981QueryMsg, Cw721HookMsg,
+    ExecuteMsg, OrderBy, PriceQueryMsg, PriceResponse, RandQueryMsg, RoyaltiesInfoResponse,
+};
 use crate::state::*;
 use cosmwasm_std::{
-    from_binary, to_binary, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    Response, StdResult, Uint128, WasmMsg, WasmQuery,
+    from_binary, to_binary, to_vec, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg,
+    Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, QueryRequest, Response,
+    StdError, StdResult, Storage, SubMsg, SystemResult, Uint128, WasmMsg, WasmQuery,
 };
-use cw20::Cw20ExecuteMsg;
-use cw721::Cw721ExecuteMsg;
+use cw1155::Cw1155ExecuteMsg;
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, TokenInfoResponse};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse, TokensResponse};
+use tiny_keccak::{Hasher as _, Keccak};
+use unicode_segmentation::UnicodeSegmentation;
 
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
-use crate::state::{AuctionStatus, AUCTIONS, CONFIG};
+use crate::state::{auctions, AuctionStatus, CONFIG};
+use cw_storage_plus::Bound;
 
 const DRAND_NEXT_ROUND_SECURITY: u64 = 10;
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+const MAX_NAME_LENGTH: usize = 64;
+const MAX_DESCRIPTION_LENGTH: usize = 1024;
+const MAX_URI_LENGTH: usize = 256;
+const MAX_TAGS: usize = 8;
+const MAX_TAG_LENGTH: usize = 32;
+
+/// Build the messages transferring lots to `recipient`.
+/// cw1155 lots of the same contract are sent in a single batch.
+fn lot_transfer_msgs<'a>(
+    env: &Env,
+    lots: impl IntoIterator<Item = &'a Lot>,
+    recipient: &str,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut msgs = vec![];
+    let mut cw1155_batches: Vec<(String, Vec<(String, Uint128)>)> = vec![];
+    for lot in lots {
+        match lot {
+            Lot::Native { denom, amount } => msgs.push(
+                BankMsg::Send {
+                    to_address: recipient.to_string(),
+                    amount: vec![Coin {
+                        denom: denom.clone(),
+                        amount: *amount,
+                    }],
+                }
+                .into(),
+            ),
+            Lot::Cw20 { contract, amount } => msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.clone(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: *amount,
+                })?,
+                funds: vec![],
+            })),
+            Lot::Cw721 { contract, token_id } => msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.clone(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: recipient.to_string(),
+                    token_id: token_id.clone(),
+                })?,
+                funds: vec![],
+            })),
+            Lot::Cw1155 {
+                contract,
+                token_id,
+                amount,
+            } => match cw1155_batches.iter_mut().find(|(addr, _)| addr == contract) {
+                Some((_, batch)) => batch.push((token_id.clone(), *amount)),
+                None => cw1155_batches.push((contract.clone(), vec![(token_id.clone(), *amount)])),
+            },
+        }
+    }
+
+    for (contract, mut batch) in cw1155_batches {
+        let msg = if batch.len() == 1 {
+            let (token_id, value) = batch.remove(0);
+            Cw1155ExecuteMsg::SendFrom {
+                from: env.contract.address.to_string(),
+                to: recipient.to_string(),
+                token_id,
+                value,
+                msg: None,
+            }
+        } else {
+            Cw1155ExecuteMsg::BatchSendFrom {
+                from: env.contract.address.to_string(),
+                to: recipient.to_string(),
+                batch,
+                msg: None,
+            }
+        };
+        msgs.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract,
+            msg: to_binary(&msg)?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(msgs)
+}
+
+/// Build the message paying `amount` of `asset` to `recipient`
+fn payment_msg(asset: &PaymentAsset, recipient: &str, amount: u128) -> StdResult<CosmosMsg> {
+    match asset.payment_type {
+        PaymentType::Coin => Ok(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: asset.payment.clone(),
+                amount: Uint128::new(amount),
+            }],
+        }
+        .into()),
+        PaymentType::Cw20 => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: asset.payment.clone(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::new(amount),
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+/// Only the owner can manage the contract
+fn assert_owner(deps: Deps, config: &Config, sender: &Addr) -> Result<(), ContractError> {
+    let owner = deps.api.addr_humanize(&config.owner)?;
+    if owner.ne(sender) {
+        return Err(ContractError::NotOwner {
+            sender: sender.to_string(),
+            owner: owner.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// The owner holds every role
+fn assert_role(
+    deps: Deps,
+    config: &Config,
+    sender: &Addr,
+    role: Role,
+) -> Result<(), ContractError> {
+    if deps.api.addr_canonicalize(sender.as_str())? == config.owner
+        || ROLES.has(deps.storage, (sender.as_str(), role.as_str()))
+    {
+        return Ok(());
+    }
+    Err(ContractError::MissingRole {
+        sender: sender.to_string(),
+        role: role.as_str().to_string(),
+    })
+}
+
+/// Check the seller can create auctions under the creation policy
+fn assert_seller_allowed(deps: Deps, config: &Config, seller: &Addr) -> Result<(), ContractError> {
+    let allowed = match &config.creation_policy {
+        CreationPolicy::Open => true,
+        CreationPolicy::Allowlist => SELLER_ALLOWLIST.has(deps.storage, seller.as_str()),
+        CreationPolicy::NftHolders { collection } => {
+            let res: TokensResponse = deps.querier.query_wasm_smart(
+                collection,
+                &Cw721QueryMsg::Tokens {
+                    owner: seller.to_string(),
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )?;
+            !res.tokens.is_empty()
+        }
+    };
+    if !allowed {
+        return Err(ContractError::SellerNotAllowed {
+            seller: seller.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn validate_eligibility(deps: Deps, eligibility: &BidderEligibility) -> StdResult<()> {
+    match eligibility {
+        BidderEligibility::Allowlist { addresses } => {
+            for address in addresses {
+                deps.api.addr_validate(address)?;
+            }
+        }
+        BidderEligibility::Cw721Holders { collection } => {
+            deps.api.addr_validate(collection)?;
+        }
+        BidderEligibility::Cw20Holders { token, .. } => {
+            deps.api.addr_validate(token)?;
+        }
+        BidderEligibility::MerkleRoot { root } => {
+            let root = hex::decode(root)
+                .map_err(|_| StdError::generic_err("Merkle root is not hex encoded"))?;
+            if root.len() != 32 {
+                return Err(StdError::generic_err("Merkle root is not a 32 bytes hash"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut hash = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// Verify `address` is a leaf of the Merkle tree of `root`
+fn verify_merkle_proof(root: &str, address: &str, proof: &[String]) -> StdResult<bool> {
+    let mut hash = keccak256(address.as_bytes());
+    for node in proof {
+        let node: [u8; 32] = hex::decode(node)
+            .ok()
+            .and_then(|node| node.try_into().ok())
+            .ok_or_else(|| StdError::generic_err("Invalid Merkle proof"))?;
+        let (a, b) = if hash <= node {
+            (hash, node)
+        } else {
+            (node, hash)
+        };
+        hash = keccak256(&[a, b].concat());
+    }
+    Ok(hex::encode(hash) == root.to_lowercase())
+}
+
+/// Check the bidder is eligible to bid on the auction
+fn assert_eligible(
+    deps: Deps,
+    auction: &Auction,
+    bidder: &str,
+    proof: Option<Vec<String>>,
+) -> Result<(), ContractError> {
+    let eligible = match &auction.eligibility {
+        None => true,
+        Some(BidderEligibility::Allowlist { addresses }) => {
+            addresses.iter().any(|address| address == bidder)
+        }
+        Some(BidderEligibility::Cw721Holders { collection }) => {
+            let res: TokensResponse = deps.querier.query_wasm_smart(
+                collection,
+                &Cw721QueryMsg::Tokens {
+                    owner: bidder.to_string(),
+                    start_after: None,
+                    limit: Some(1),
+                },
+            )?;
+            !res.tokens.is_empty()
+        }
+        Some(BidderEligibility::Cw20Holders { token, min_balance }) => {
+            let res: BalanceResponse = deps.querier.query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: bidder.to_string(),
+                },
+            )?;
+            res.balance >= *min_balance
+        }
+        Some(BidderEligibility::MerkleRoot { root }) => {
+            verify_merkle_proof(root, bidder, &proof.unwrap_or_default())?
+        }
+    };
+    if !eligible {
+        return Err(ContractError::NotEligible {
+            bidder: bidder.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check the auction name and metadata lengths, in graphemes
+fn validate_metadata(name: &str, metadata: &AuctionMetadata) -> Result<(), ContractError> {
+    let check = |field: &str, value: &str, max: usize| {
+        if value.graphemes(true).count() > max {
+            return Err(ContractError::InvalidMetadata {
+                field: field.to_string(),
+                max,
+            });
+        }
+        Ok(())
+    };
+    check("name", name, MAX_NAME_LENGTH)?;
+    check(
+        "description",
+        metadata.description.as_deref().unwrap_or_default(),
+        MAX_DESCRIPTION_LENGTH,
+    )?;
+    check(
+        "image",
+        metadata.image.as_deref().unwrap_or_default(),
+        MAX_URI_LENGTH,
+    )?;
+    check(
+        "external_url",
+        metadata.external_url.as_deref().unwrap_or_default(),
+        MAX_URI_LENGTH,
+    )?;
+    if metadata.tags.len() > MAX_TAGS {
+        return Err(ContractError::InvalidMetadata {
+            field: "tags".to_string(),
+            max: MAX_TAGS,
+        });
+    }
+    for tag in metadata.tags.iter() {
+        check("tags", tag, MAX_TAG_LENGTH)?;
+    }
+    Ok(())
+}
+
+/// Snapshot of the cw721 `NftInfo` of a lot, none if the collection does not answer it
+fn lot_metadata(deps: Deps, collection: &Addr, token_id: &str) -> Option<LotMetadata> {
+    #[derive(serde::Deserialize)]
+    struct TokenUri {
+        token_uri: Option<String>,
+    }
+
+    let request: QueryRequest<Empty> = WasmQuery::Smart {
+        contract_addr: collection.to_string(),
+        msg: to_binary(&Cw721QueryMsg::NftInfo {
+            token_id: token_id.to_string(),
+        })
+        .ok()?,
+    }
+    .into();
+    let nft_info = match deps.querier.raw_query(&to_vec(&request).ok()?) {
+        SystemResult::Ok(ContractResult::Ok(nft_info)) => nft_info,
+        _ => return None,
+    };
+    let TokenUri { token_uri } = from_binary(&nft_info).ok()?;
+    Some(LotMetadata {
+        contract: collection.to_string(),
+        token_id: token_id.to_string(),
+        token_uri,
+        nft_info,
+    })
+}
+
+/// Check the duration is within the config bounds
+fn validate_duration(config: &Config, duration: u64) -> Result<(), ContractError> {
+    if duration < config.min_auction_duration || duration > config.max_auction_duration {
+        return Err(ContractError::InvalidDuration {
+            duration,
+            min: config.min_auction_duration,
+            max: config.max_auction_duration,
+        });
+    }
+    Ok(())
+}
+
+/// Check the lot contract is an allowed collection and the auction
+/// satisfies the collection requirements
+fn check_collection(deps: Deps, contract: &Addr, auction: &Auction) -> Result<(), ContractError> {
+    let collection = COLLECTIONS
+        .may_load(deps.storage, contract.as_str())?
+        .ok_or_else(|| ContractError::BadRequest {
+            msg: "Unsupport contract!".to_string(),
+        })?;
+
+    if let Some(min_duration) = collection.min_duration {
+        if auction.auction_duration < min_duration {
+            return Err(ContractError::DurationTooShort {
+                input_duration: auction.auction_duration,
+                min_duration,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Re-check the collection requirements of every deposited lot, after the
+/// auction settings changed
+fn check_lot_collections(deps: Deps, auction: &Auction) -> Result<(), ContractError> {
+    for lot in auction.lots.iter() {
+        match lot {
+            Lot::Native { .. } => continue,
+            Lot::Cw20 { contract, .. }
+            | Lot::Cw721 { contract, .. }
+            | Lot::Cw1155 { contract, .. } => {
+                check_collection(deps, &Addr::unchecked(contract), auction)?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `lot_deposited` event of a lot added to an auction
+fn lot_event(auction_id: u64, lot: &Lot) -> Event {
+    let event = Event::new("lot_deposited").add_attribute("auction_id", auction_id.to_string());
+    match lot {
+        Lot::Native { denom, amount } => event
+            .add_attribute("lot_type", "native")
+            .add_attribute("asset", denom)
+            .add_attribute("amount", amount.to_string()),
+        Lot::Cw20 { contract, amount } => event
+            .add_attribute("lot_type", "cw20")
+            .add_attribute("asset", contract)
+            .add_attribute("amount", amount.to_string()),
+        Lot::Cw721 { contract, token_id } => event
+            .add_attribute("lot_type", "cw721")
+            .add_attribute("asset", contract)
+            .add_attribute("token_id", token_id),
+        Lot::Cw1155 {
+            contract,
+            token_id,
+            amount,
+        } => event
+            .add_attribute("lot_type", "cw1155")
+            .add_attribute("asset", contract)
+            .add_attribute("token_id", token_id)
+            .add_attribute("amount", amount.to_string()),
+    }
+}
+
+/// Event of a payment of `amount` in `asset` to `recipient` for an auction
+fn payment_event(
+    ty: &str,
+    auction_id: u64,
+    recipient_key: &str,
+    recipient: &str,
+    asset: &PaymentAsset,
+    amount: u128,
+) -> Event {
+    Event::new(ty)
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute(recipient_key, recipient)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("asset", &asset.payment)
+}
+
+/// Fails if the operations of `scope` are paused.
+/// Bidding is also stopped while the auctions are disabled.
+fn assert_not_paused(
+    storage: &dyn Storage,
+    config: &Config,
+    scope: PauseScope,
+) -> Result<(), ContractError> {
+    if scope == PauseScope::Bidding && !config.enable_auction {
+        return Err(ContractError::AuctionDisabled {});
+    }
+    match PAUSED.may_load(storage, scope.as_str())? {
+        Some(pause) => Err(ContractError::Paused {
+            scope: scope.as_str().to_string(),
+            reason: pause.reason,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Update the house statistics and the statistics of the seller
+pub(crate) fn update_stats(
+    storage: &mut dyn Storage,
+    seller: &str,
+    update: impl Fn(&mut AuctionStats),
+) -> StdResult<()> {
+    let mut stats = STATS.may_load(storage)?.unwrap_or_default();
+    update(&mut stats);
+    STATS.save(storage, &stats)?;
+
+    let mut stats = seller_stats()
+        .may_load(storage, seller)?
+        .unwrap_or_default();
+    update(&mut stats);
+    seller_stats().save(storage, seller, &stats)
+}
+
+/// Add a received cw721 token to the auction lots
+fn add_cw721_lot(
+    deps: DepsMut,
+    env: &Env,
+    collection: &Addr,
+    auction_id: u64,
+    mut auction: Auction,
+    token_id: String,
+) -> Result<Event, ContractError> {
+    check_collection(deps.as_ref(), collection, &auction)?;
+
+    let lot = Lot::Cw721 {
+        contract: collection.to_string(),
+        token_id: token_id.clone(),
+    };
+    if !auction.expects(&lot) {
+        return Err(ContractError::UnexpectedLot {});
+    }
+    auction
+        .lot_metadata
+        .extend(lot_metadata(deps.as_ref(), collection, &token_id));
+
+    // the token must have been transferred to the contract
+    let res: OwnerOfResponse = deps.querier.query_wasm_smart(
+        collection,
+        &Cw721QueryMsg::OwnerOf {
+            token_id,
+            include_expired: None,
+        },
+    )?;
+    if res.owner != env.contract.address.as_str() {
+        return Err(ContractError::BadRequest {
+            msg: "Token not received".to_string(),
+        });
+    }
+
+    let event = lot_event(auction_id, &lot);
+    auction.lots.push(lot);
+    auctions().save(deps.storage, auction_id, &auction)?;
+    Ok(event)
+}
+
+/// Royalties of the lots sold together for `sale_price`, split evenly between them.
+/// A cw721 lot pays its cw2981 royalty, other lots or cw721 contracts not
+/// implementing cw2981, or answering an invalid recipient, pay the royalty
+/// configured for the collection. Every royalty is capped at `max_royalty` of the lot price.
+fn lot_royalties(
+    deps: Deps,
+    config: &Config,
+    lots: &[&Lot],
+    sale_price: u128,
+) -> StdResult<Vec<(String, u128)>> {
+    if lots.is_empty() {
+        return Ok(vec![]);
+    }
+    let lot_price = Uint128::new(sale_price / lots.len() as u128);
+    let cap = lot_price * config.max_royalty;
+
+    let mut royalties = vec![];
+    for lot in lots {
+        let (contract, token_id) = match lot {
+            Lot::Native { .. } => continue,
+            Lot::Cw20 { contract, .. } | Lot::Cw1155 { contract, .. } => (contract, None),
+            Lot::Cw721 { contract, token_id } => (contract, Some(token_id)),
+        };
+
+        let cw2981 = token_id.and_then(|token_id| {
+            let msg = Cw2981QueryMsg::Extension {
+                msg: Cw2981Query::RoyaltyInfo {
+                    token_id: token_id.clone(),
+                    sale_price: lot_price,
+                },
+            };
+            deps.querier
+                .query_wasm_smart::<RoyaltiesInfoResponse>(contract, &msg)
+                .ok()
+                // an invalid recipient would revert the whole settlement
+                .filter(|res| deps.api.addr_validate(&res.address).is_ok())
+        });
+        let royalty = match cw2981 {
+            Some(res) => Some((res.address, res.royalty_amount)),
+            None => COLLECTIONS
+                .may_load(deps.storage, contract)?
+                .and_then(|collection| collection.royalty)
+                .map(|royalty| (royalty.recipient, lot_price * royalty.share)),
+        };
+
+        if let Some((recipient, amount)) = royalty {
+            let amount = amount.min(cap);
+            if !amount.is_zero() && !recipient.is_empty() {
+                royalties.push((recipient, amount.u128()));
+            }
+        }
+    }
+    Ok(royalties)
+}
+
+/// Check the denom format, or that the cw20 address is a cw20 token contract,
+/// and that the payment is allowed when payments are restricted.
+fn validate_payment(
+    deps: Deps,
+    config: &Config,
+    asset: &PaymentAsset,
+) -> Result<(), ContractError> {
+    let invalid = || ContractError::InvalidPayment {
+        payment: asset.payment.clone(),
+    };
+
+    match asset.payment_type {
+        PaymentType::Coin => {
+            // same rule as the cosmos-sdk: [a-zA-Z][a-zA-Z0-9/:._-]{2,127}
+            let denom = asset.payment.as_bytes();
+            let valid = (3..=128).contains(&denom.len())
+                && denom[0].is_ascii_alphabetic()
+                && denom
+                    .iter()
+                    .all(|c| c.is_ascii_alphanumeric() || b"/:._-".contains(c));
+            if !valid {
+                return Err(invalid());
+            }
+        }
+        PaymentType::Cw20 => {
+            let contract = deps
+                .api
+                .addr_validate(&asset.payment)
+                .map_err(|_| invalid())?;
+            if contract.as_str() != asset.payment {
+                return Err(invalid());
+            }
+            deps.querier
+                .query_wasm_smart::<TokenInfoResponse>(contract, &Cw20QueryMsg::TokenInfo {})
+                .map_err(|_| invalid())?;
+        }
+    }
+
+    if config.restrict_payments
+        && PAYMENT_ALLOWLIST.may_load(deps.storage, &asset.payment)?
+            != Some(asset.payment_type.clone())
+    {
+        return Err(ContractError::UnsupportedPayment {
+            payment: asset.payment.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Candle end time of an ended auction, drawn from the oracle randomness
+/// once and then kept on the auction
+fn candle_end_time(
+    deps: Deps,
+    config: &Config,
+    auction_id: u64,
+    auction: &Auction,
+) -> StdResult<u64> {
+    if let Some(end_time) = auction.candle_end_time {
+        return Ok(end_time);
+    }
+
+    let rand_key = auction_id + DRAND_NEXT_ROUND_SECURITY;
+
+    let msg = RandQueryMsg::Get { round: rand_key };
+    let wasm = WasmQuery::Smart {
+        contract_addr: deps.api.addr_humanize(&config.oracle_contract)?.to_string(),
+        msg: to_binary(&msg)?,
+    };
+
+    let res: crate::msg::GetResponse = deps.querier.query(&wasm.into())?;
+    let mut hasher = DefaultHasher::new();
+    res.randomness.hash(&mut hasher);
+
+    // a zero duration auction ends at its start
+    let offset = hasher
+        .finish()
+        .checked_rem(auction.auction_duration)
+        .unwrap_or_default();
+    Ok(offset.saturating_add(auction.start_timestmap))
+}
+
+/// Value a bid in the auction payment.
+/// Other accepted payments are converted with the price oracle rate.
+fn bid_value(
+    deps: Deps,
+    config: &Config,
+    auction: &Auction,
+    asset: &PaymentAsset,
+    amount: u128,
+) -> Result<u128, ContractError> {
+    if auction.payment_asset().eq(asset) {
+        return Ok(amount);
+    }
+
+    let price_oracle = config
+        .price_oracle
+        .as_ref()
+        .ok_or_else(|| ContractError::BadRequest {
+            msg: "Price oracle not configured".to_string(),
+        })?;
+
+    let msg = PriceQueryMsg::Price {
+        base: asset.payment.clone(),
+        quote: auction.payment.clone(),
+    };
+    let wasm = WasmQuery::Smart {
+        contract_addr: deps.api.addr_humanize(price_oracle)?.to_string(),
+        msg: to_binary(&msg)?,
+    };
+
+    let res: PriceResponse = deps.querier.query(&wasm.into())?;
+    Ok((Uint128::new(amount) * res.rate).u128())
+}
 
 pub mod execute {
     //{{{
@@ -30,8 +699,16 @@ pub mod execute {
         payment_type: PaymentType,
         payment: String,
         min_price: Option<u128>,
+        mode: Option<AuctionMode>,
+        accepted_payments: Option<Vec<PaymentAsset>>,
+        lots_expected: Option<Vec<Lot>>,
+        eligibility: Option<BidderEligibility>,
+        metadata: Option<AuctionMetadata>,
     ) -> Result<Response, ContractError> {
         let mut config = CONFIG.load(deps.storage)?;
+        assert_not_paused(deps.storage, &config, PauseScope::Creation)?;
+        assert_seller_allowed(deps.as_ref(), &config, &info.sender)?;
+        validate_duration(&config, auction_duration)?;
         let now = env.block.time.seconds();
         if now > start_timestmap + auction_duration {
             return Err(ContractError::BadRequest {
@@ -39,30 +716,96 @@ pub mod execute {
             });
         }
 
-        // TODO
-        // auction params precheck
-        // check contract has been received those tokens
+        let payment_asset = PaymentAsset {
+            payment_type: payment_type.clone(),
+            payment: payment.clone(),
+        };
+        validate_payment(deps.as_ref(), &config, &payment_asset)?;
+
+        let accepted_payments = accepted_payments.unwrap_or_default();
+        for asset in accepted_payments.iter() {
+            validate_payment(deps.as_ref(), &config, asset)?;
+        }
+        if !accepted_payments.is_empty() && config.price_oracle.is_none() {
+            return Err(ContractError::BadRequest {
+                msg: "Price oracle not configured".to_string(),
+            });
+        }
+
+        let lots: Vec<Lot> = info
+            .funds
+            .iter()
+            .filter(|coin| !coin.amount.is_zero())
+            .map(|coin| Lot::Native {
+                denom: coin.denom.clone(),
+                amount: coin.amount,
+            })
+            .collect();
+
+        if let Some(eligibility) = eligibility.as_ref() {
+            validate_eligibility(deps.as_ref(), eligibility)?;
+        }
+        let metadata = metadata.unwrap_or_default();
+        validate_metadata(&name, &metadata)?;
+
         let auction = Auction {
             name,
             start_timestmap,
             auction_duration,
             bidders: vec![],
             curr_winner: None,
-            tokens: vec![],
+            lots,
             seller: deps.api.addr_canonicalize(info.sender.as_str())?,
             payment_type,
             payment,
+            accepted_payments,
             min_price,
             bid_num: 0,
             is_candle_blow: false,
+            mode: mode.unwrap_or_default(),
+            winners: vec![],
+            lots_expected: lots_expected.unwrap_or_default(),
+            candle_end_time: None,
+            eligibility,
+            metadata,
+            lot_metadata: vec![],
+        };
+
+        // attached coins are deposited lots
+        let mut expected = Auction {
+            lots: vec![],
+            ..auction.clone()
         };
+        for lot in auction.lots.iter() {
+            if !expected.expects(lot) {
+                return Err(ContractError::UnexpectedLot {});
+            }
+            expected.lots.push(lot.clone());
+        }
 
-        AUCTIONS.save(deps.storage, config.auction_num, &auction)?;
+        auctions().save(deps.storage, config.auction_num, &auction)?;
+
+        let mut events = vec![Event::new("auction_created")
+            .add_attribute("auction_id", config.auction_num.to_string())
+            .add_attribute("seller", info.sender.as_str())
+            .add_attribute("start_time", auction.start_timestmap.to_string())
+            .add_attribute("end_time", auction.end_timestamp().to_string())
+            .add_attribute("asset", &auction.payment)];
+        events.extend(
+            auction
+                .lots
+                .iter()
+                .map(|lot| lot_event(config.auction_num, lot)),
+        );
 
         config.auction_num += 1;
         CONFIG.save(deps.storage, &config)?;
+        update_stats(deps.storage, info.sender.as_str(), |stats| {
+            stats.created += 1
+        })?;
 
         Ok(Response::new()
+            .add_events(events)
             .add_attribute("method", "auction")
             .add_attribute("name", auction.name)
             .add_attribute("start_timestmap", auction.start_timestmap.to_string())
@@ -81,107 +824,158 @@ pub mod execute {
         info: MessageInfo,
         bidder: Option<String>,
         auction_id: u64,
+        proof: Option<Vec<String>>,
     ) -> Result<Response, ContractError> {
         let config = CONFIG.load(deps.storage)?;
-        assert!(config.enable_auction, "Auction disabled");
-        let mut auction = AUCTIONS.may_load(deps.storage, auction_id)?.unwrap();
-        assert_eq!(
-            auction.payment_type,
-            PaymentType::Coin,
-            "Unsupport coin bid"
-        );
+        assert_not_paused(deps.storage, &config, PauseScope::Bidding)?;
+        let mut auction = auctions().may_load(deps.storage, auction_id)?.unwrap();
         let now = env.block.time.seconds();
 
         if !auction.status(now).eq(&AuctionStatus::OpeningPeriod) {
             return Err(ContractError::NotOpeningPeriod {
                 start: auction.start_timestmap,
-                end: auction
-                    .start_timestmap
-                    .checked_add(auction.auction_duration)
-                    .unwrap_or(u64::MAX),
+                end: auction.end_timestamp(),
             });
         }
 
-        // check bid price
-        let default_fund = &Coin {
-            denom: auction.payment.clone(),
-            amount: Uint128::from(0u128),
-        };
+        if !auction.lots_ready() {
+            return Err(ContractError::LotsNotDeposited {});
+        }
 
-        let fund = info
-            .funds
-            .iter()
-            .find(|fund| fund.denom.eq(&auction.payment))
-            .unwrap_or(default_fund);
+        // the single attached coin is the bid, nothing else stays unrecorded
+        let fund = match info.funds.as_slice() {
+            [fund] => fund,
+            _ => {
+                return Err(ContractError::BadRequest {
+                    msg: "Bid with exactly one coin".to_string(),
+                })
+            }
+        };
+        let asset = PaymentAsset {
+            payment_type: PaymentType::Coin,
+            payment: fund.denom.clone(),
+        };
+        let amount = fund.amount.u128();
+        if !auction.accepts(&asset) {
+            return Err(ContractError::UnsupportedPayment {
+                payment: asset.payment,
+            });
+        }
 
+        let value = bid_value(deps.as_ref(), &config, &auction, &asset, amount)?;
         let min_price = auction.bid_min_price();
 
-        if fund.amount.u128() < min_price {
+        if value < min_price {
             return Err(ContractError::AuctionPriceTooLow {
                 min_price,
-                current: fund.amount.u128(),
+                current: value,
             });
         }
 
-        let bidder = bidder.unwrap_or_else(|| info.sender.to_string());
+        let bidder = match bidder {
+            Some(bidder) => deps.api.addr_validate(&bidder)?,
+            None => info.sender.clone(),
+        };
+        assert_eligible(deps.as_ref(), &auction, bidder.as_str(), proof)?;
+        BIDDER_AUCTIONS.save(deps.storage, (bidder.as_str(), auction_id), &Empty {})?;
+
+        let payer = (bidder != info.sender).then(|| info.sender);
+        let mut event = payment_event(
+            "bid_placed",
+            auction_id,
+            "bidder",
+            bidder.as_str(),
+            &asset,
+            amount,
+        )
+        .add_attribute("value", value.to_string());
+        if let Some(payer) = payer.as_ref() {
+            event = event.add_attribute("payer", payer);
+        }
 
         // Update auction status
-        auction.bid_num += 1;
-        auction
-            .bidders
-            .push((bidder.clone(), now, fund.amount.u128()));
-        auction.curr_winner = Some((bidder, now, fund.amount.u128()));
-
-        AUCTIONS.save(deps.storage, auction_id, &auction)?;
-
-        Ok(Response::new())
+        auction.place_bid(Bid {
+            bidder,
+            payer,
+            timestamp: now,
+            value,
+            asset,
+            amount,
+            refunded: false,
+        });
+
+        auctions().save(deps.storage, auction_id, &auction)?;
+        let seller = deps.api.addr_humanize(&auction.seller)?;
+        update_stats(deps.storage, seller.as_str(), |stats| stats.bids += 1)?;
+
+        Ok(Response::new().add_event(event))
     }
 
-    pub fn winner_claim(
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_auction(
         deps: DepsMut,
         env: Env,
         info: MessageInfo,
         auction_id: u64,
-        winner: Option<String>,
+        name: Option<String>,
+        start_timestamp: Option<u64>,
+        duration: Option<u64>,
+        min_price: Option<u128>,
+        metadata: Option<AuctionMetadata>,
     ) -> Result<Response, ContractError> {
-        let auction = AUCTIONS.may_load(deps.storage, auction_id)?.unwrap();
-
-        let winner = winner.unwrap_or_else(|| info.sender.to_string());
-
-        assert_eq!(
-            auction.status(env.block.time.seconds()),
-            AuctionStatus::Ended,
-            "Auction is not ended"
-        );
-
-        if auction.curr_winner.is_none() || !auction.curr_winner.as_ref().unwrap().0.eq(&winner) {
-            return Err(ContractError::BadRequest {
-                msg: "Not Winner".to_string(),
+        let config = CONFIG.load(deps.storage)?;
+        let mut auction = auctions().load(deps.storage, auction_id)?;
+        let seller = deps.api.addr_humanize(&auction.seller)?;
+        if info.sender != seller {
+            return Err(ContractError::NotOwner {
+                sender: info.sender.to_string(),
+                owner: seller.to_string(),
             });
         }
 
-        let mut messages: Vec<CosmosMsg> = vec![];
-        // Transfer all nft
-        for token in auction.tokens.clone() {
-            let transfer_nft_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: token.0,
-                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
-                    recipient: winner.clone(),
-                    token_id: token.1,
-                })?,
-                funds: vec![],
-            });
-
-            messages.push(transfer_nft_msg);
+        let now = env.block.time.seconds();
+        if auction.status(now) != AuctionStatus::NotStarted || !auction.bidders.is_empty() {
+            return Err(ContractError::AuctionLocked {});
         }
 
-        AUCTIONS.save(deps.storage, auction_id, &auction)?;
+        if let Some(name) = name {
+            auction.name = name;
+        }
+        if let Some(start_timestamp) = start_timestamp {
+            auction.start_timestmap = start_timestamp;
+        }
+        if let Some(duration) = duration {
+            validate_duration(&config, duration)?;
+            auction.auction_duration = duration;
+            check_lot_collections(deps.as_ref(), &auction)?;
+        }
+        if let Some(min_price) = min_price {
+            auction.min_price = Some(min_price);
+        }
+        if let Some(metadata) = metadata {
+            auction.metadata = metadata;
+        }
+        validate_metadata(&auction.name, &auction.metadata)?;
+        if now > auction.end_timestamp() {
+            return Err(ContractError::BadRequest {
+                msg: "Bad timestamp setting".to_string(),
+            });
+        }
 
-        // TODO:transfer the denom to seller
+        auctions().save(deps.storage, auction_id, &auction)?;
 
+        let event = Event::new("auction_updated")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("name", &auction.name)
+            .add_attribute("start_time", auction.start_timestmap.to_string())
+            .add_attribute("end_time", auction.end_timestamp().to_string())
+            .add_attribute(
+                "min_price",
+                auction.min_price.unwrap_or_default().to_string(),
+            );
         Ok(Response::new()
-            .add_attribute("method", "winner_claim")
-            .add_messages(messages))
+            .add_event(event)
+            .add_attribute("method", "update_auction"))
     }
 
     pub fn _handle_cw721(
@@ -192,17 +986,9 @@ pub mod execute {
         token_id: String,
         msg: Binary,
     ) -> Result<Response, ContractError> {
-        let config = CONFIG.load(deps.storage)?;
-
-        if !config.support_contract.contains(&info.sender.to_string()) {
-            return Err(ContractError::BadRequest {
-                msg: "Unsupport contract!".to_string(),
-            });
-        }
-
         let auction_msg: AuctionMsg = from_binary(&msg)?;
 
-        let mut auction = AUCTIONS.load(deps.storage, auction_msg.id)?;
+        let auction = auctions().load(deps.storage, auction_msg.id)?;
         let now = env.block.time.seconds();
 
         assert_eq!(
@@ -217,11 +1003,166 @@ pub mod execute {
             "Not owner"
         );
 
-        auction.tokens.push((info.sender.to_string(), token_id));
+        let event = add_cw721_lot(deps, &env, &info.sender, auction_msg.id, auction, token_id)?;
+
+        Ok(Response::new()
+            .add_event(event)
+            .add_attribute("method", "_handle_cw721"))
+    }
+
+    /// Create an auction of the seller and deposit the received token
+    /// as its lot in the same transaction. The auction may start right away.
+    #[allow(clippy::too_many_arguments)]
+    pub fn _handle_cw721_create_auction(
+        mut deps: DepsMut,
+        info: MessageInfo,
+        env: Env,
+        sender: String,
+        token_id: String,
+        name: String,
+        start_timestamp: u64,
+        duration: u64,
+        payment_type: PaymentType,
+        payment: String,
+        min_price: Option<u128>,
+        mode: Option<AuctionMode>,
+        accepted_payments: Option<Vec<PaymentAsset>>,
+        lots_expected: Option<Vec<Lot>>,
+        eligibility: Option<BidderEligibility>,
+        metadata: Option<AuctionMetadata>,
+    ) -> Result<Response, ContractError> {
+        let seller_info = MessageInfo {
+            sender: deps.api.addr_validate(&sender)?,
+            funds: vec![],
+        };
+        let res = auction(
+            deps.branch(),
+            env.clone(),
+            seller_info,
+            name,
+            start_timestamp,
+            duration,
+            payment_type,
+            payment,
+            min_price,
+            mode,
+            accepted_payments,
+            lots_expected,
+            eligibility,
+            metadata,
+        )?;
+
+        let auction_id = CONFIG.load(deps.storage)?.auction_num - 1;
+        let auction = auctions().load(deps.storage, auction_id)?;
+        let event = add_cw721_lot(
+            deps,
+            &env,
+            &info.sender,
+            auction_id,
+            auction,
+            token_id.clone(),
+        )?;
+
+        Ok(res
+            .add_event(event)
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("token_id", token_id))
+    }
+
+    /// handle receive cw1155 tokens deposited as auction lots.
+    /// Every (token_id, amount) pair of the batch is a lot.
+    pub fn _handle_cw1155(
+        deps: DepsMut,
+        info: MessageInfo,
+        env: Env,
+        from: String,
+        batch: Vec<(String, Uint128)>,
+        msg: Binary,
+    ) -> Result<Response, ContractError> {
+        let auction_msg: AuctionMsg = from_binary(&msg)?;
+
+        let mut auction = auctions().load(deps.storage, auction_msg.id)?;
+        check_collection(deps.as_ref(), &info.sender, &auction)?;
+        let now = env.block.time.seconds();
+
+        if auction.status(now) != AuctionStatus::NotStarted {
+            return Err(ContractError::BadRequest {
+                msg: "Lots can only be deposited before the auction start".to_string(),
+            });
+        }
+
+        if deps.api.addr_humanize(&auction.seller)?.as_str() != from {
+            return Err(ContractError::NotOwner {
+                sender: from,
+                owner: deps.api.addr_humanize(&auction.seller)?.to_string(),
+            });
+        }
+
+        let mut events = vec![];
+        for (token_id, amount) in batch {
+            let lot = Lot::Cw1155 {
+                contract: info.sender.to_string(),
+                token_id,
+                amount,
+            };
+            if !auction.expects(&lot) {
+                return Err(ContractError::UnexpectedLot {});
+            }
+            events.push(lot_event(auction_msg.id, &lot));
+            auction.lots.push(lot);
+        }
+
+        auctions().save(deps.storage, auction_msg.id, &auction)?;
+
+        Ok(Response::new()
+            .add_events(events)
+            .add_attribute("method", "_handle_cw1155")
+            .add_attribute("auction_id", auction_msg.id.to_string()))
+    }
+
+    /// handle receive cw20 token deposited as an auction lot
+    pub fn _handle_cw20_lot(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        sender: String,
+        amount: Uint128,
+        auction_id: u64,
+    ) -> Result<Response, ContractError> {
+        let mut auction = auctions().load(deps.storage, auction_id)?;
+        check_collection(deps.as_ref(), &info.sender, &auction)?;
+        let now = env.block.time.seconds();
+
+        if auction.status(now) != AuctionStatus::NotStarted {
+            return Err(ContractError::BadRequest {
+                msg: "Lots can only be deposited before the auction start".to_string(),
+            });
+        }
+
+        if deps.api.addr_humanize(&auction.seller)?.as_str() != sender {
+            return Err(ContractError::NotOwner {
+                sender,
+                owner: deps.api.addr_humanize(&auction.seller)?.to_string(),
+            });
+        }
+
+        let lot = Lot::Cw20 {
+            contract: info.sender.to_string(),
+            amount,
+        };
+        if !auction.expects(&lot) {
+            return Err(ContractError::UnexpectedLot {});
+        }
+        let event = lot_event(auction_id, &lot);
+        auction.lots.push(lot);
 
-        AUCTIONS.save(deps.storage, auction_msg.id, &auction)?;
+        auctions().save(deps.storage, auction_id, &auction)?;
 
-        Ok(Response::new().add_attribute("method", "_handle_cw721"))
+        Ok(Response::new()
+            .add_event(event)
+            .add_attribute("method", "_handle_cw20_lot")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("amount", amount))
     }
 
     /// handle receive cw20 token bid request
@@ -239,43 +1180,81 @@ pub mod execute {
         msg: Binary,
     ) -> Result<Response, ContractError> {
         let config = CONFIG.load(deps.storage)?;
-        assert!(config.enable_auction, "Auction has been disabled!");
+        assert_not_paused(deps.storage, &config, PauseScope::Bidding)?;
 
         let auction_msg: AuctionMsg = from_binary(&msg)?;
 
-        let mut auction = AUCTIONS.load(deps.storage, auction_msg.id)?;
+        let mut auction = auctions().load(deps.storage, auction_msg.id)?;
 
         let now = env.block.time.seconds();
 
-        assert_eq!(
-            info.sender.to_string(),
-            auction.payment,
-            "Unsupport cw20 bid"
-        );
+        let asset = PaymentAsset {
+            payment_type: PaymentType::Cw20,
+            payment: info.sender.to_string(),
+        };
+        if !auction.accepts(&asset) {
+            return Err(ContractError::UnsupportedPayment {
+                payment: asset.payment,
+            });
+        }
         assert_eq!(
             auction.status(now),
             AuctionStatus::OpeningPeriod,
             "Cannot bid right now"
         );
 
+        if !auction.lots_ready() {
+            return Err(ContractError::LotsNotDeposited {});
+        }
+
+        let value = bid_value(deps.as_ref(), &config, &auction, &asset, amount.u128())?;
         let min_price = auction.bid_min_price();
 
         // check recv amount gt min price
-        if amount.u128() < min_price {
+        if value < min_price {
             return Err(ContractError::AuctionPriceTooLow {
                 min_price,
-                current: amount.u128(),
+                current: value,
             });
         }
 
-        let bidder = auction_msg.bidder.unwrap_or(sender);
-        auction.curr_winner = Some((bidder.clone(), now, amount.u128()));
-        auction.bid_num += 1;
-        auction.bidders.push((bidder, now, amount.u128()));
-
-        AUCTIONS.save(deps.storage, auction_msg.id, &auction)?;
+        let sender = deps.api.addr_validate(&sender)?;
+        let bidder = match auction_msg.bidder {
+            Some(bidder) => deps.api.addr_validate(&bidder)?,
+            None => sender.clone(),
+        };
+        assert_eligible(deps.as_ref(), &auction, bidder.as_str(), auction_msg.proof)?;
+        BIDDER_AUCTIONS.save(deps.storage, (bidder.as_str(), auction_msg.id), &Empty {})?;
+        let payer = (bidder != sender).then(|| sender);
+        let mut event = payment_event(
+            "bid_placed",
+            auction_msg.id,
+            "bidder",
+            bidder.as_str(),
+            &asset,
+            amount.u128(),
+        )
+        .add_attribute("value", value.to_string());
+        if let Some(payer) = payer.as_ref() {
+            event = event.add_attribute("payer", payer);
+        }
+        auction.place_bid(Bid {
+            bidder,
+            payer,
+            timestamp: now,
+            value,
+            asset,
+            amount: amount.u128(),
+            refunded: false,
+        });
+
+        auctions().save(deps.storage, auction_msg.id, &auction)?;
+        let seller = deps.api.addr_humanize(&auction.seller)?;
+        update_stats(deps.storage, seller.as_str(), |stats| stats.bids += 1)?;
 
-        Ok(Response::new().add_attribute("method", "handle_cw20_bid"))
+        Ok(Response::new()
+            .add_event(event)
+            .add_attribute("method", "handle_cw20_bid"))
     }
 
     pub fn blow_candle(
@@ -284,124 +1263,166 @@ pub mod execute {
         auction_id: u64,
     ) -> Result<Response, ContractError> {
         let config = CONFIG.load(deps.storage)?;
-        let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+        assert_not_paused(deps.storage, &config, PauseScope::Settlement)?;
+        let mut auction = auctions().load(deps.storage, auction_id)?;
         let now = env.block.time.seconds();
         assert_eq!(
             auction.status(now),
             AuctionStatus::Ended,
             "Auction status is now ended"
         );
+        if auction.is_candle_blow {
+            return Err(ContractError::AuctionAlreadySettled {});
+        }
 
         assert!(auction.curr_winner.is_some(), "Auction flow");
 
-        let rand_key = auction_id + DRAND_NEXT_ROUND_SECURITY;
-
-        let msg = RandQueryMsg::Get { round: rand_key };
-        let wasm = WasmQuery::Smart {
-            contract_addr: deps.api.addr_humanize(&config.oracle_contract)?.to_string(),
-            msg: to_binary(&msg)?,
-        };
-
-        let res: crate::msg::GetResponse = deps.querier.query(&wasm.into())?;
-        let mut hasher = DefaultHasher::new();
-        res.randomness.hash(&mut hasher);
+        let end_time = candle_end_time(deps.as_ref(), &config, auction_id, &auction)?;
+        let winners = auction.candle_winners(end_time);
+        let seller = deps.api.addr_humanize(&auction.seller)?.to_string();
+
+        // Amount paid by each bid, in the asset it bid with
+        let mut paid_by = vec![0u128; auction.bidders.len()];
+        for (idx, price) in winners.iter() {
+            let bid = &auction.bidders[*idx];
+            paid_by[*idx] = if *price < bid.value {
+                Uint128::new(bid.amount)
+                    .multiply_ratio(*price, bid.value)
+                    .u128()
+            } else {
+                bid.amount
+            };
+        }
 
-        let offset = hasher.finish() % auction.auction_duration;
+        // Refund the non-winners not refunded yet and the winners overpayment.
+        let mut payment_msgs = vec![];
+        let mut events = vec![Event::new("candle_blown")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("end_time", end_time.to_string())];
+        for (idx, bid) in auction.bidders.iter().enumerate().rev() {
+            if !bid.refunded && bid.amount > paid_by[idx] {
+                let refund = bid.amount - paid_by[idx];
+                payment_msgs.push(payment_msg(&bid.asset, bid.payer().as_str(), refund)?);
+                events.push(payment_event(
+                    "refund_issued",
+                    auction_id,
+                    "payer",
+                    bid.payer().as_str(),
+                    &bid.asset,
+                    refund,
+                ));
+            }
+        }
 
-        let mut bank_msgs = vec![];
-        let mut cw20_refund_msg = vec![];
+        // Transfer the lots by winner rank, unsold lots go back to the seller.
+        // Every winner payment is split between the lot royalties, the protocol
+        // fee and the seller, paid in the asset the winner bid with.
+        let lots_per_winner = match auction.mode {
+            AuctionMode::Single => usize::MAX,
+            AuctionMode::Batch { .. } => 1,
+        };
+        let mut proceeds: Vec<(PaymentAsset, u128)> = vec![];
+        let mut royalty_msgs = vec![];
         let mut winner_msg = vec![];
-
-        let end_time = offset
-            .checked_add(auction.start_timestmap)
-            .unwrap_or(u64::MAX);
-
-        auction.curr_winner = None;
-
-        for (bidder, bid_time, amount) in auction.bidders.iter().rev() {
-            if *bid_time <= end_time && auction.curr_winner.is_none() {
-                auction.curr_winner = Some((bidder.clone(), *bid_time, *amount));
-                // Transfer all nft
-                for token in auction.tokens.clone() {
-                    let transfer_nft_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: token.0,
-                        msg: to_binary(&Cw721ExecuteMsg::TransferNft {
-                            recipient: bidder.clone(),
-                            token_id: token.1,
-                        })?,
-                        funds: vec![],
-                    });
-
-                    winner_msg.push(transfer_nft_msg);
+        let mut lots = auction.lots.iter();
+        for (idx, _) in winners.iter() {
+            let bid = &auction.bidders[*idx];
+            let won: Vec<&Lot> = lots.by_ref().take(lots_per_winner).collect();
+            winner_msg.extend(lot_transfer_msgs(
+                &env,
+                won.iter().copied(),
+                bid.bidder.as_str(),
+            )?);
+
+            let paid = paid_by[*idx];
+            let fee = Uint128::new(paid)
+                .multiply_ratio(config.fee_rate, 100u128)
+                .u128();
+            events.push(
+                payment_event(
+                    "auction_settled",
+                    auction_id,
+                    "winner",
+                    bid.bidder.as_str(),
+                    &bid.asset,
+                    paid,
+                )
+                .add_attribute("fee", fee.to_string()),
+            );
+            let mut remaining = paid - fee;
+            for (recipient, amount) in lot_royalties(deps.as_ref(), &config, &won, paid)? {
+                let amount = amount.min(remaining);
+                if amount > 0 {
+                    remaining -= amount;
+                    royalty_msgs.push(payment_msg(&bid.asset, &recipient, amount)?);
                 }
-                continue;
             }
-            // make
-            match auction.payment_type {
-                PaymentType::Coin => {
-                    bank_msgs.push(BankMsg::Send {
-                        to_address: bidder.clone(),
-                        amount: vec![Coin {
-                            denom: auction.payment.clone(),
-                            amount: Uint128::new(*amount),
-                        }],
-                    });
-                }
-                PaymentType::Cw20 => {
-                    let refund_msg = Cw20ExecuteMsg::Transfer {
-                        recipient: bidder.clone(),
-                        amount: Uint128::new(*amount),
-                    };
-
-                    let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: auction.payment.clone(),
-                        msg: to_binary(&refund_msg)?,
-                        funds: vec![],
-                    });
-                    cw20_refund_msg.push(msg);
+
+            if fee > 0 {
+                FEES.update(
+                    deps.storage,
+                    &bid.asset.payment,
+                    |collected| -> StdResult<_> {
+                        let (_, collected) =
+                            collected.unwrap_or((bid.asset.payment_type.clone(), Uint128::zero()));
+                        Ok((
+                            bid.asset.payment_type.clone(),
+                            collected + Uint128::new(fee),
+                        ))
+                    },
+                )?;
+            }
+            if remaining > 0 {
+                match proceeds.iter_mut().find(|(asset, _)| asset.eq(&bid.asset)) {
+                    Some((_, total)) => *total += remaining,
+                    None => proceeds.push((bid.asset.clone(), remaining)),
                 }
             }
         }
+        winner_msg.extend(lot_transfer_msgs(&env, lots, &seller)?);
+
         // made transfer payment to seller
-        if auction.curr_winner.is_some() {
-            let seller = deps.api.addr_humanize(&auction.seller)?.to_string();
-            let amount = auction.curr_winner.as_ref().unwrap().2;
-            match auction.payment_type {
-                PaymentType::Coin => {
-                    bank_msgs.push(BankMsg::Send {
-                        to_address: seller,
-                        amount: vec![Coin {
-                            denom: auction.payment.clone(),
-                            amount: Uint128::new(amount),
-                        }],
-                    });
-                }
-                PaymentType::Cw20 => {
-                    let refund_msg = Cw20ExecuteMsg::Transfer {
-                        recipient: seller,
-                        amount: Uint128::new(amount),
-                    };
-
-                    let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                        contract_addr: auction.payment.clone(),
-                        msg: to_binary(&refund_msg)?,
-                        funds: vec![],
-                    });
-                    cw20_refund_msg.push(msg);
-                }
-            }
+        for (asset, amount) in proceeds.iter() {
+            payment_msgs.push(payment_msg(asset, &seller, *amount)?);
         }
+        payment_msgs.extend(royalty_msgs);
+
+        auction.winners = winners
+            .iter()
+            .map(|(idx, price)| {
+                let bid = &auction.bidders[*idx];
+                (bid.bidder.to_string(), bid.timestamp, *price)
+            })
+            .collect();
+        auction.curr_winner = auction.winners.first().cloned();
+        auction.candle_end_time = Some(end_time);
 
         auction.is_candle_blow = true;
 
-        AUCTIONS.save(deps.storage, auction_id, &auction)?;
+        auctions().save(deps.storage, auction_id, &auction)?;
+
+        let volume: Vec<(&PaymentAsset, u128)> = winners
+            .iter()
+            .map(|(idx, _)| (&auction.bidders[*idx].asset, paid_by[*idx]))
+            .collect();
+        // no bid before the candle end: the auction passed
+        update_stats(deps.storage, &seller, |stats| {
+            match volume.is_empty() {
+                true => stats.passed += 1,
+                false => stats.settled += 1,
+            }
+            for (asset, amount) in volume.iter() {
+                stats.add_volume(asset, *amount);
+            }
+        })?;
 
         Ok(Response::new()
-            .add_messages(bank_msgs)
-            .add_messages(cw20_refund_msg)
+            .add_events(events)
+            .add_messages(payment_msgs)
             .add_messages(winner_msg))
     }
 
+    use cw1155::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
     use cw20::Cw20ReceiveMsg;
 
     pub fn receive_cw20(
@@ -410,7 +1431,12 @@ pub mod execute {
         info: MessageInfo,
         msg: Cw20ReceiveMsg,
     ) -> Result<Response, ContractError> {
-        _handle_cw20_bid(deps, env, info, msg.sender, msg.amount, msg.msg)
+        match from_binary(&msg.msg) {
+            Ok(Cw20HookMsg::DepositLot { auction_id }) => {
+                _handle_cw20_lot(deps, env, info, msg.sender, msg.amount, auction_id)
+            }
+            Err(_) => _handle_cw20_bid(deps, env, info, msg.sender, msg.amount, msg.msg),
+        }
     }
 
     pub fn receive_cw721(
@@ -419,46 +1445,745 @@ pub mod execute {
         info: MessageInfo,
         msg: cw721::Cw721ReceiveMsg,
     ) -> Result<Response, ContractError> {
-        _handle_cw721(deps, info, env, msg.sender, msg.token_id, msg.msg)
+        match from_binary(&msg.msg) {
+            Ok(Cw721HookMsg::CreateAuction {
+                name,
+                start_timestamp,
+                duration,
+                payment_type,
+                payment,
+                min_price,
+                mode,
+                accepted_payments,
+                lots_expected,
+                eligibility,
+                metadata,
+            }) => _handle_cw721_create_auction(
+                deps,
+                info,
+                env,
+                msg.sender,
+                msg.token_id,
+                name,
+                start_timestamp,
+                duration,
+                payment_type,
+                payment,
+                min_price,
+                mode,
+                accepted_payments,
+                lots_expected,
+                eligibility,
+                metadata,
+            ),
+            Err(_) => _handle_cw721(deps, info, env, msg.sender, msg.token_id, msg.msg),
+        }
     }
 
-    /// If the auction was flow. return the token of the seller
-    pub fn auction_flow(
+    pub fn receive_cw1155(
         deps: DepsMut,
         env: Env,
-        auction_id: u64,
+        info: MessageInfo,
+        msg: Cw1155ReceiveMsg,
     ) -> Result<Response, ContractError> {
-        let mut auction = AUCTIONS.load(deps.storage, auction_id)?;
+        let from = msg.from.unwrap_or(msg.operator);
+        _handle_cw1155(
+            deps,
+            info,
+            env,
+            from,
+            vec![(msg.token_id, msg.amount)],
+            msg.msg,
+        )
+    }
+
+    pub fn batch_receive_cw1155(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: Cw1155BatchReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        let from = msg.from.unwrap_or(msg.operator);
+        _handle_cw1155(deps, info, env, from, msg.batch, msg.msg)
+    }
+
+    /// Refund the non-winning bids of an ended auction ahead of its settlement,
+    /// by anyone. Only the `Refunds` pause applies, not the `Settlement` one.
+    pub fn claim_refund(
+        deps: DepsMut,
+        env: Env,
+        auction_id: u64,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_not_paused(deps.storage, &config, PauseScope::Refunds)?;
+        let mut auction = auctions().load(deps.storage, auction_id)?;
         let now = env.block.time.seconds();
-        assert_eq!(
-            auction.status(now),
-            AuctionStatus::Ended,
+        if auction.status(now) != AuctionStatus::Ended || auction.is_candle_blow {
+            return Err(ContractError::BadRequest {
+                msg: "Refunds are claimed after the end, before the settlement".to_string(),
+            });
+        }
+        // nothing to refund, the candle end is left to draw
+        if auction.bidders.is_empty() {
+            return Ok(Response::new().add_attribute("method", "claim_refund"));
+        }
+
+        let end_time = candle_end_time(deps.as_ref(), &config, auction_id, &auction)?;
+        let winners: Vec<usize> = auction
+            .candle_winners(end_time)
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut msgs = vec![];
+        let mut events = vec![];
+        for (idx, bid) in auction.bidders.iter_mut().enumerate() {
+            if bid.refunded || winners.contains(&idx) {
+                continue;
+            }
+            bid.refunded = true;
+            msgs.push(payment_msg(&bid.asset, bid.payer().as_str(), bid.amount)?);
+            events.push(payment_event(
+                "refund_issued",
+                auction_id,
+                "payer",
+                bid.payer().as_str(),
+                &bid.asset,
+                bid.amount,
+            ));
+        }
+        auction.candle_end_time = Some(end_time);
+        auctions().save(deps.storage, auction_id, &auction)?;
+
+        Ok(Response::new()
+            .add_events(events)
+            .add_messages(msgs)
+            .add_attribute("method", "claim_refund"))
+    }
+
+    /// If the auction was flow. return the token of the seller.
+    /// An auction opened without all its lots is cancelled the same way.
+    pub fn auction_flow(
+        deps: DepsMut,
+        env: Env,
+        auction_id: u64,
+    ) -> Result<Response, ContractError> {
+        let mut auction = auctions().load(deps.storage, auction_id)?;
+        let now = env.block.time.seconds();
+        let status = auction.status(now);
+        let cancelled = status == AuctionStatus::OpeningPeriod && !auction.lots_ready();
+        assert!(
+            status == AuctionStatus::Ended || cancelled,
             "auction status is not done"
         );
 
         assert!(auction.curr_winner.is_none(), "Auction not flow");
         assert!(!auction.is_candle_blow, "Auction is not blow right now");
 
+        let seller = deps.api.addr_humanize(&auction.seller)?.to_string();
+        let msgs = lot_transfer_msgs(&env, auction.lots.iter(), &seller)?;
+
+        auction.is_candle_blow = true;
+        auctions().save(deps.storage, auction_id, &auction)?;
+        update_stats(deps.storage, &seller, |stats| match cancelled {
+            true => stats.cancelled += 1,
+            false => stats.passed += 1,
+        })?;
+
+        // a passed auction is settled without winner
+        let event = match cancelled {
+            true => Event::new("auction_cancelled"),
+            false => Event::new("auction_settled"),
+        }
+        .add_attribute("auction_id", auction_id.to_string());
+
+        Ok(Response::new().add_event(event).add_messages(msgs))
+    }
+
+    /// Settle the ended auctions by end time: blow the candle of the auctions
+    /// with bids, flow the others. The keeper earns `keeper_reward` percent
+    /// of the protocol fees collected by the batch.
+    pub fn settle_batch(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_not_paused(deps.storage, &config, PauseScope::Settlement)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let now = env.block.time.seconds();
+
+        // resume after an auction, e.g. one failing to settle
+        let min = match start_after {
+            Some(id) => {
+                let end_time = auctions().load(deps.storage, id)?.end_timestamp();
+                Some(Bound::exclusive((end_time, id)))
+            }
+            None => None,
+        };
+        let ended = auctions()
+            .idx
+            .settle_queue
+            .sub_prefix(0u8)
+            .keys(
+                deps.storage,
+                min,
+                Some(Bound::exclusive((now, 0u64))),
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // each auction settles or fails on its own
         let mut msgs = vec![];
-        for (addr, token_id) in auction.tokens.clone() {
-            let refund_token_msg = Cw721ExecuteMsg::TransferNft {
-                recipient: deps.api.addr_humanize(&auction.seller)?.to_string(),
-                token_id,
+        for auction_id in ended.iter() {
+            let msg = WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_binary(&ExecuteMsg::KeeperSettle {
+                    auction_id: *auction_id,
+                    keeper: info.sender.to_string(),
+                })?,
+                funds: vec![],
             };
+            msgs.push(SubMsg::reply_on_error(msg, *auction_id));
+        }
 
-            let msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: addr,
-                msg: to_binary(&refund_token_msg)?,
-                funds: vec![],
+        Ok(Response::new()
+            .add_submessages(msgs)
+            .add_attribute("method", "settle_batch")
+            .add_attribute("batched", ended.len().to_string()))
+    }
+
+    /// Blow or flow an auction of a `SettleBatch`, the keeper receives
+    /// `keeper_reward` percent of the protocol fee
+    pub fn keeper_settle(
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        keeper: String,
+    ) -> Result<Response, ContractError> {
+        if info.sender != env.contract.address {
+            return Err(ContractError::Unauthorized {
+                description: Some("Only dispatched by SettleBatch".to_string()),
             });
+        }
+        let config = CONFIG.load(deps.storage)?;
+        let keeper = deps.api.addr_validate(&keeper)?;
+        let auction = auctions().load(deps.storage, auction_id)?;
+
+        let fees_before = FEES
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut res = match auction.curr_winner {
+            Some(_) => blow_candle(deps.branch(), env, auction_id)?,
+            None => auction_flow(deps.branch(), env, auction_id)?,
+        };
 
-            msgs.push(msg);
+        // reward the keeper from the fees of the settlement
+        if config.keeper_reward > 0 {
+            let fees_after = FEES
+                .range(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (payment, (payment_type, collected)) in fees_after {
+                let before = fees_before
+                    .iter()
+                    .find(|(p, _)| *p == payment)
+                    .map(|(_, (_, amount))| *amount)
+                    .unwrap_or_default();
+                let reward = (collected - before).multiply_ratio(config.keeper_reward, 100u128);
+                if reward.is_zero() {
+                    continue;
+                }
+                FEES.save(
+                    deps.storage,
+                    &payment,
+                    &(payment_type.clone(), collected - reward),
+                )?;
+                let asset = PaymentAsset {
+                    payment_type,
+                    payment,
+                };
+                res = res
+                    .add_message(payment_msg(&asset, keeper.as_str(), reward.u128())?)
+                    .add_event(
+                        Event::new("keeper_rewarded")
+                            .add_attribute("keeper", keeper.as_str())
+                            .add_attribute("amount", reward.to_string())
+                            .add_attribute("asset", &asset.payment),
+                    );
+            }
         }
 
-        auction.is_candle_blow = true;
-        AUCTIONS.save(deps.storage, auction_id, &auction)?;
+        Ok(res.add_attribute("method", "keeper_settle"))
+    }
+
+    /// Reply of a `SettleBatch` auction failing to settle, the batch goes on
+    pub fn settle_failed(auction_id: u64, error: String) -> Result<Response, ContractError> {
+        let event = Event::new("settle_failed")
+            .add_attribute("auction_id", auction_id.to_string())
+            .add_attribute("error", error);
+        Ok(Response::new()
+            .add_event(event)
+            .add_attribute("method", "settle_failed"))
+    }
+
+    /// Move the lots of a passed auction to a new auction, they stay held by the contract
+    pub fn relist(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        auction_id: u64,
+        start_timestamp: u64,
+        duration: u64,
+        min_price: Option<u128>,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        assert_not_paused(deps.storage, &config, PauseScope::Creation)?;
+        assert_seller_allowed(deps.as_ref(), &config, &info.sender)?;
+        let mut passed = auctions().load(deps.storage, auction_id)?;
+        let seller = deps.api.addr_humanize(&passed.seller)?;
+        if info.sender != seller {
+            return Err(ContractError::NotOwner {
+                sender: info.sender.to_string(),
+                owner: seller.to_string(),
+            });
+        }
+
+        let now = env.block.time.seconds();
+        if passed.status(now) != AuctionStatus::Ended
+            || !passed.bidders.is_empty()
+            || passed.is_candle_blow
+            || !passed.lots_ready()
+        {
+            return Err(ContractError::AuctionNotPassed {});
+        }
+
+        validate_duration(&config, duration)?;
+        if now > start_timestamp + duration {
+            return Err(ContractError::BadRequest {
+                msg: "Bad timestamp setting".to_string(),
+            });
+        }
+
+        // a fresh auction over the same lots, no bid nor candle end carried over
+        let auction = Auction {
+            name: passed.name.clone(),
+            start_timestmap: start_timestamp,
+            auction_duration: duration,
+            bidders: vec![],
+            curr_winner: None,
+            lots: passed.lots.clone(),
+            seller: passed.seller.clone(),
+            payment_type: passed.payment_type.clone(),
+            payment: passed.payment.clone(),
+            accepted_payments: passed.accepted_payments.clone(),
+            min_price: min_price.or(passed.min_price),
+            bid_num: 0,
+            is_candle_blow: false,
+            mode: passed.mode.clone(),
+            winners: vec![],
+            lots_expected: vec![],
+            candle_end_time: None,
+            eligibility: passed.eligibility.clone(),
+            metadata: passed.metadata.clone(),
+            lot_metadata: passed.lot_metadata.clone(),
+        };
+        // the collections and payments may have been restricted since
+        validate_payment(deps.as_ref(), &config, &auction.payment_asset())?;
+        for asset in auction.accepted_payments.iter() {
+            validate_payment(deps.as_ref(), &config, asset)?;
+        }
+        check_lot_collections(deps.as_ref(), &auction)?;
+
+        let new_id = config.auction_num;
+        auctions().save(deps.storage, new_id, &auction)?;
+        config.auction_num += 1;
+        CONFIG.save(deps.storage, &config)?;
+
+        // the passed auction is settled without winner, its lots moved over
+        passed.is_candle_blow = true;
+        passed.lots = vec![];
+        passed.lot_metadata = vec![];
+        auctions().save(deps.storage, auction_id, &passed)?;
+        update_stats(deps.storage, seller.as_str(), |stats| {
+            stats.passed += 1;
+            stats.created += 1;
+        })?;
+
+        let events = vec![
+            Event::new("auction_settled").add_attribute("auction_id", auction_id.to_string()),
+            Event::new("auction_created")
+                .add_attribute("auction_id", new_id.to_string())
+                .add_attribute("seller", seller.as_str())
+                .add_attribute("start_time", auction.start_timestmap.to_string())
+                .add_attribute("end_time", auction.end_timestamp().to_string())
+                .add_attribute("asset", &auction.payment)
+                .add_attribute("relisted_from", auction_id.to_string()),
+        ];
+        Ok(Response::new()
+            .add_events(events)
+            .add_attribute("method", "relist")
+            .add_attribute("auction_id", new_id.to_string()))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_config(
+        deps: DepsMut,
+        info: MessageInfo,
+        min_auction_duration: Option<u64>,
+        max_auction_duration: Option<u64>,
+        enable_auction: Option<bool>,
+        fee_rate: Option<u64>,
+        default_denom: Option<String>,
+        price_oracle: Option<String>,
+        restrict_payments: Option<bool>,
+        max_royalty: Option<Decimal>,
+        keeper_reward: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        assert_owner(deps.as_ref(), &config, &info.sender)?;
+
+        if let Some(min_auction_duration) = min_auction_duration {
+            config.min_auction_duration = min_auction_duration;
+        }
+        if let Some(max_auction_duration) = max_auction_duration {
+            config.max_auction_duration = max_auction_duration;
+        }
+        if let Some(enable_auction) = enable_auction {
+            config.enable_auction = enable_auction;
+        }
+        if let Some(fee_rate) = fee_rate {
+            config.fee_rate = fee_rate;
+        }
+        if let Some(max_royalty) = max_royalty {
+            config.max_royalty = max_royalty;
+        }
+        if let Some(default_denom) = default_denom {
+            config.default_denom = default_denom;
+        }
+        if let Some(price_oracle) = price_oracle {
+            config.price_oracle = Some(deps.api.addr_canonicalize(&price_oracle)?);
+        }
+        if let Some(restrict_payments) = restrict_payments {
+            config.restrict_payments = restrict_payments;
+        }
+        if let Some(keeper_reward) = keeper_reward {
+            config.keeper_reward = keeper_reward;
+        }
+        config.validate()?;
+
+        CONFIG.save(deps.storage, &config)?;
+
+        let event = Event::new("config_updated")
+            .add_attribute(
+                "min_auction_duration",
+                config.min_auction_duration.to_string(),
+            )
+            .add_attribute(
+                "max_auction_duration",
+                config.max_auction_duration.to_string(),
+            )
+            .add_attribute("enable_auction", config.enable_auction.to_string())
+            .add_attribute("fee_rate", config.fee_rate.to_string())
+            .add_attribute("default_denom", config.default_denom)
+            .add_attribute("restrict_payments", config.restrict_payments.to_string())
+            .add_attribute("max_royalty", config.max_royalty.to_string())
+            .add_attribute("keeper_reward", config.keeper_reward.to_string());
+
+        Ok(Response::new()
+            .add_event(event)
+            .add_attribute("method", "update_config"))
+    }
+
+    /// Allow lots of the collection, or update its settings
+    pub fn add_collection(
+        deps: DepsMut,
+        info: MessageInfo,
+        contract: String,
+        royalty: Option<Royalty>,
+        min_duration: Option<u64>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        let contract = deps.api.addr_validate(&contract)?;
+        if let Some(royalty) = royalty.as_ref() {
+            deps.api.addr_validate(&royalty.recipient)?;
+            if royalty.share > Decimal::one() {
+                return Err(ContractError::BadRequest {
+                    msg: "Royalty share greater than 1".to_string(),
+                });
+            }
+        }
+
+        COLLECTIONS.save(
+            deps.storage,
+            contract.as_str(),
+            &CollectionInfo {
+                royalty,
+                min_duration,
+            },
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_collection")
+            .add_attribute("contract", contract))
+    }
+
+    pub fn remove_collection(
+        deps: DepsMut,
+        info: MessageInfo,
+        contract: String,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        COLLECTIONS.remove(deps.storage, &contract);
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_collection")
+            .add_attribute("contract", contract))
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_owner(deps.as_ref(), &config, &info.sender)?;
+
+        let address = deps.api.addr_validate(&address)?;
+        ROLES.save(deps.storage, (address.as_str(), role.as_str()), &role)?;
+
+        Ok(Response::new().add_event(
+            Event::new("role_granted")
+                .add_attribute("address", address)
+                .add_attribute("role", role.as_str()),
+        ))
+    }
+
+    pub fn revoke_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        address: String,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_owner(deps.as_ref(), &config, &info.sender)?;
+
+        ROLES.remove(deps.storage, (&address, role.as_str()));
+
+        Ok(Response::new().add_event(
+            Event::new("role_revoked")
+                .add_attribute("address", address)
+                .add_attribute("role", role.as_str()),
+        ))
+    }
+
+    pub fn update_creation_policy(
+        deps: DepsMut,
+        info: MessageInfo,
+        policy: CreationPolicy,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        if let CreationPolicy::NftHolders { collection } = &policy {
+            deps.api.addr_validate(collection)?;
+        }
+        config.creation_policy = policy;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new().add_attribute("method", "update_creation_policy"))
+    }
+
+    pub fn add_sellers(
+        deps: DepsMut,
+        info: MessageInfo,
+        sellers: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        for seller in sellers.iter() {
+            let seller = deps.api.addr_validate(seller)?;
+            SELLER_ALLOWLIST.save(deps.storage, seller.as_str(), &Empty {})?;
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "add_sellers")
+            .add_attribute("sellers", sellers.join(",")))
+    }
+
+    pub fn remove_sellers(
+        deps: DepsMut,
+        info: MessageInfo,
+        sellers: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        for seller in sellers.iter() {
+            SELLER_ALLOWLIST.remove(deps.storage, seller);
+        }
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_sellers")
+            .add_attribute("sellers", sellers.join(",")))
+    }
+
+    pub fn update_fee_rate(
+        deps: DepsMut,
+        info: MessageInfo,
+        fee_rate: u64,
+    ) -> Result<Response, ContractError> {
+        let mut config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::FeeManager)?;
+
+        config.fee_rate = fee_rate;
+        config.validate()?;
+        CONFIG.save(deps.storage, &config)?;
+
+        Ok(Response::new().add_event(
+            Event::new("config_updated").add_attribute("fee_rate", fee_rate.to_string()),
+        ))
+    }
+
+    /// Withdraw collected protocol fees, all of them to the sender by default
+    pub fn withdraw_fees(
+        deps: DepsMut,
+        info: MessageInfo,
+        payment: String,
+        amount: Option<Uint128>,
+        recipient: Option<String>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::FeeManager)?;
+
+        let (payment_type, collected) = FEES.load(deps.storage, &payment)?;
+        let amount = amount.unwrap_or(collected);
+        if amount > collected {
+            return Err(ContractError::InsufficientFund {
+                amount: collected,
+                required: amount,
+            });
+        }
+        let recipient = match recipient {
+            Some(recipient) => deps.api.addr_validate(&recipient)?,
+            None => info.sender,
+        };
+
+        FEES.save(
+            deps.storage,
+            &payment,
+            &(payment_type.clone(), collected - amount),
+        )?;
+        let asset = PaymentAsset {
+            payment_type,
+            payment,
+        };
+        let msg = payment_msg(&asset, recipient.as_str(), amount.u128())?;
+
+        Ok(Response::new().add_message(msg).add_event(
+            Event::new("fees_withdrawn")
+                .add_attribute("recipient", recipient)
+                .add_attribute("amount", amount.to_string())
+                .add_attribute("asset", asset.payment),
+        ))
+    }
+
+    /// Pause operations, by the guardian
+    pub fn pause(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        scopes: Vec<PauseScope>,
+        reason: String,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Guardian)?;
+
+        let mut events = vec![];
+        for scope in scopes {
+            let pause = PauseInfo {
+                scope,
+                reason: reason.clone(),
+                paused_by: info.sender.to_string(),
+                paused_at: env.block.time.seconds(),
+            };
+            PAUSED.save(deps.storage, scope.as_str(), &pause)?;
+            events.push(
+                Event::new("paused")
+                    .add_attribute("scope", scope.as_str())
+                    .add_attribute("reason", &reason),
+            );
+        }
+
+        Ok(Response::new()
+            .add_events(events)
+            .add_attribute("method", "pause"))
+    }
+
+    /// Resume paused operations, by the owner
+    pub fn unpause(
+        deps: DepsMut,
+        info: MessageInfo,
+        scopes: Vec<PauseScope>,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_owner(deps.as_ref(), &config, &info.sender)?;
+
+        let mut events = vec![];
+        for scope in scopes {
+            PAUSED.remove(deps.storage, scope.as_str());
+            events.push(Event::new("unpaused").add_attribute("scope", scope.as_str()));
+        }
+
+        Ok(Response::new()
+            .add_events(events)
+            .add_attribute("method", "unpause"))
+    }
+
+    pub fn add_payment_asset(
+        deps: DepsMut,
+        info: MessageInfo,
+        asset: PaymentAsset,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        // validated as an unrestricted payment, it is not allowed yet
+        let unrestricted = Config {
+            restrict_payments: false,
+            ..config
+        };
+        validate_payment(deps.as_ref(), &unrestricted, &asset)?;
+
+        PAYMENT_ALLOWLIST.save(deps.storage, &asset.payment, &asset.payment_type)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "add_payment_asset")
+            .add_attribute("payment", asset.payment))
+    }
 
-        Ok(Response::new().add_messages(msgs))
+    pub fn remove_payment_asset(
+        deps: DepsMut,
+        info: MessageInfo,
+        payment: String,
+    ) -> Result<Response, ContractError> {
+        let config = CONFIG.load(deps.storage)?;
+        assert_role(deps.as_ref(), &config, &info.sender, Role::Operator)?;
+
+        PAYMENT_ALLOWLIST.remove(deps.storage, &payment);
+
+        Ok(Response::new()
+            .add_attribute("method", "remove_payment_asset")
+            .add_attribute("payment", payment))
     }
 } //}}}
 
@@ -475,73 +2200,448 @@ pub mod query {
             enable_auction: config.enable_auction,
             fee_rate: config.fee_rate,
             default_denom: config.default_denom,
-            support_contract: config.support_contract,
+            restrict_payments: config.restrict_payments,
+            max_royalty: config.max_royalty,
+            owner: deps.api.addr_humanize(&config.owner)?,
+            oracle_contract: deps.api.addr_humanize(&config.oracle_contract)?,
+            price_oracle: config
+                .price_oracle
+                .map(|oracle| deps.api.addr_humanize(&oracle))
+                .transpose()?,
+            creation_policy: config.creation_policy,
+            keeper_reward: config.keeper_reward,
+        })
+    }
+
+    pub fn sellers(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<String>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        SELLER_ALLOWLIST
+            .keys(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
+    pub fn roles(deps: Deps, address: String) -> StdResult<response::Roles> {
+        let config = CONFIG.load(deps.storage)?;
+        let address = deps.api.addr_validate(&address)?;
+        let roles = ROLES
+            .prefix(address.as_str())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, role)| role))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(response::Roles {
+            owner: deps.api.addr_canonicalize(address.as_str())? == config.owner,
+            address,
+            roles,
+        })
+    }
+
+    pub fn fees(deps: Deps) -> StdResult<Vec<(PaymentAsset, Uint128)>> {
+        FEES.range(deps.storage, None, None, Order::Ascending)
+            .map(|item| {
+                let (payment, (payment_type, collected)) = item?;
+                let asset = PaymentAsset {
+                    payment_type,
+                    payment,
+                };
+                Ok((asset, collected))
+            })
+            .collect()
+    }
+
+    pub fn pause_state(deps: Deps) -> StdResult<response::PauseState> {
+        let config = CONFIG.load(deps.storage)?;
+        let paused = PAUSED
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, pause)| pause))
+            .collect::<StdResult<Vec<_>>>()?;
+        Ok(response::PauseState {
+            enable_auction: config.enable_auction,
+            paused,
         })
     }
 
+    pub fn collections(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<response::Collection>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        COLLECTIONS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (contract, info) = item?;
+                Ok(response::Collection {
+                    contract,
+                    royalty: info.royalty,
+                    min_duration: info.min_duration,
+                })
+            })
+            .collect()
+    }
+
+    pub fn payment_assets(
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<PaymentAsset>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.as_deref().map(Bound::exclusive);
+
+        PAYMENT_ALLOWLIST
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                let (payment, payment_type) = item?;
+                Ok(PaymentAsset {
+                    payment_type,
+                    payment,
+                })
+            })
+            .collect()
+    }
+
+    /// Auctions newest first. The settled filter is served by the `settled`
+    /// index, running auctions are looked up among the unsettled ones.
     pub fn auction_list(
         deps: Deps,
         env: Env,
         status: Option<AuctionStatus>,
-        page: u32,
-        limit: u32,
+        settled: Option<bool>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     ) -> StdResult<Option<Vec<response::Auction>>> {
-        let config = CONFIG.load(deps.storage)?;
-        let start_amount = page * limit;
-        let mut count = 0;
-        let mut res = vec![];
-
-        for i in (0..(config.auction_num as usize)).rev() {
-            let auction = AUCTIONS.load(deps.storage, i as u64)?;
-            if let Some(status) = status.to_owned() {
-                if auction.status(env.block.time.seconds()) != status {
-                    continue;
-                }
-            }
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let now = env.block.time.seconds();
 
-            if count >= start_amount {
-                res.push(response::Auction {
-                    name: auction.name,
-                    start_timestamp: auction.start_timestmap,
-                    auction_duration: auction.auction_duration,
-                    bidders: auction.bidders,
-                    curr_winner: auction.curr_winner,
-                    tokens: auction.tokens,
-                    seller: deps.api.addr_humanize(&auction.seller)?,
-                    payment_type: auction.payment_type,
-                    payment: auction.payment,
-                    min_price: auction.min_price,
-                    bid_num: auction.bid_num,
-                });
-            }
+        let settled = settled.or(match status {
+            Some(AuctionStatus::NotStarted) | Some(AuctionStatus::OpeningPeriod) => Some(false),
+            _ => None,
+        });
+        let max = start_after.map(Bound::exclusive);
+        let items = match settled {
+            Some(settled) => auctions().idx.settled.prefix(settled as u8).range(
+                deps.storage,
+                None,
+                max,
+                Order::Descending,
+            ),
+            None => auctions().range(deps.storage, None, max, Order::Descending),
+        };
 
-            if res.len() >= limit as usize {
-                break;
-            }
+        let res = items
+            .filter(|item| match (item, status.as_ref()) {
+                (Ok((_, auction)), Some(status)) => auction.status(now).eq(status),
+                _ => true,
+            })
+            .take(limit)
+            .map(|item| {
+                let (id, auction) = item?;
+                auction_response(deps, now, id, auction)
+            })
+            .collect::<StdResult<Vec<_>>>()?;
 
-            count += 1;
+        Ok(Some(res))
+    }
+
+    pub fn auction(deps: Deps, env: Env, auction_id: u64) -> StdResult<Option<response::Auction>> {
+        let now = env.block.time.seconds();
+        match auctions().may_load(deps.storage, auction_id)? {
+            Some(auction) => Ok(Some(auction_response(deps, now, auction_id, auction)?)),
+            None => Ok(None),
         }
+    }
 
-        Ok(Some(res))
+    /// Auctions of a seller, newest first
+    pub fn auctions_by_seller(
+        deps: Deps,
+        env: Env,
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<response::Auction>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let now = env.block.time.seconds();
+        let seller = deps.api.addr_canonicalize(&seller)?;
+
+        auctions()
+            .idx
+            .seller
+            .prefix(seller.to_vec())
+            .range(
+                deps.storage,
+                None,
+                start_after.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|item| {
+                let (id, auction) = item?;
+                auction_response(deps, now, id, auction)
+            })
+            .collect()
     }
 
-    pub fn auction(deps: Deps, auction_id: u64) -> StdResult<Option<response::Auction>> {
-        let auction_res = AUCTIONS.load(deps.storage, auction_id);
-        match auction_res {
-            Ok(auction) => Ok(Some(response::Auction {
-                name: auction.name,
-                start_timestamp: auction.start_timestmap,
-                auction_duration: auction.auction_duration,
-                bidders: auction.bidders,
-                curr_winner: auction.curr_winner,
-                tokens: auction.tokens,
-                seller: deps.api.addr_humanize(&auction.seller)?,
-                payment_type: auction.payment_type,
-                payment: auction.payment,
-                min_price: auction.min_price,
-                bid_num: auction.bid_num,
-            })),
-            Err(_) => Ok(None),
+    /// Auctions of a payment, newest first
+    pub fn auctions_by_payment(
+        deps: Deps,
+        env: Env,
+        payment: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<response::Auction>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let now = env.block.time.seconds();
+
+        auctions()
+            .idx
+            .payment
+            .prefix(payment)
+            .range(
+                deps.storage,
+                None,
+                start_after.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|item| {
+                let (id, auction) = item?;
+                auction_response(deps, now, id, auction)
+            })
+            .collect()
+    }
+
+    /// Auctions selling lots of a collection, newest first
+    pub fn auctions_by_collection(
+        deps: Deps,
+        env: Env,
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<response::Auction>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let now = env.block.time.seconds();
+
+        auctions()
+            .idx
+            .collection
+            .auction_ids(
+                deps.storage,
+                collection,
+                None,
+                start_after.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|id| {
+                let id = id?;
+                auction_response(deps, now, id, auctions().load(deps.storage, id)?)
+            })
+            .collect()
+    }
+
+    /// Participation of a bidder in the auctions it bid on, newest first
+    pub fn bids_by_bidder(
+        deps: Deps,
+        env: Env,
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<response::BidderAuction>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let now = env.block.time.seconds();
+
+        BIDDER_AUCTIONS
+            .prefix(&address)
+            .keys(
+                deps.storage,
+                None,
+                start_after.map(Bound::exclusive),
+                Order::Descending,
+            )
+            .take(limit)
+            .map(|id| {
+                let auction_id = id?;
+                let auction = auctions().load(deps.storage, auction_id)?;
+
+                // Bids leading right now, or winning at the candle end once drawn
+                let leading: Vec<usize> = if auction.is_candle_blow {
+                    vec![]
+                } else {
+                    auction
+                        .candle_winners(auction.candle_end_time.unwrap_or(u64::MAX))
+                        .into_iter()
+                        .map(|(idx, _)| idx)
+                        .collect()
+                };
+
+                let mut bid_num = 0u32;
+                let mut highest_bid: Option<Bid> = None;
+                let mut locked: Vec<(PaymentAsset, Uint128)> = vec![];
+                for bid in auction.bidders.iter() {
+                    if bid.bidder.as_str() != address {
+                        continue;
+                    }
+                    bid_num += 1;
+                    if highest_bid
+                        .as_ref()
+                        .map_or(true, |high| bid.value > high.value)
+                    {
+                        highest_bid = Some(bid.clone());
+                    }
+                    // Payments and refunds are sent at settlement
+                    if auction.is_candle_blow || bid.refunded {
+                        continue;
+                    }
+                    match locked.iter_mut().find(|(asset, _)| asset.eq(&bid.asset)) {
+                        Some((_, total)) => *total += Uint128::new(bid.amount),
+                        None => locked.push((bid.asset.clone(), Uint128::new(bid.amount))),
+                    }
+                }
+
+                let leading = if auction.is_candle_blow {
+                    auction
+                        .winners
+                        .iter()
+                        .any(|(winner, _, _)| winner.eq(&address))
+                } else {
+                    leading
+                        .iter()
+                        .any(|idx| auction.bidders[*idx].bidder.as_str() == address)
+                };
+
+                Ok(response::BidderAuction {
+                    auction_id,
+                    status: auction.status(now),
+                    settled: auction.is_candle_blow,
+                    bid_num,
+                    highest_bid,
+                    leading,
+                    locked,
+                })
+            })
+            .collect()
+    }
+
+    /// Bids of an auction, paged by bid index
+    pub fn bids(
+        deps: Deps,
+        auction_id: u64,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    ) -> StdResult<Vec<response::AuctionBid>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let auction = auctions().load(deps.storage, auction_id)?;
+
+        let bids =
+            auction
+                .bidders
+                .into_iter()
+                .enumerate()
+                .map(|(index, bid)| response::AuctionBid {
+                    index: index as u32,
+                    bid,
+                });
+        let res = match order.unwrap_or(OrderBy::Asc) {
+            OrderBy::Asc => bids
+                .filter(|bid| start_after.map_or(true, |start| bid.index > start))
+                .take(limit)
+                .collect(),
+            OrderBy::Desc => bids
+                .rev()
+                .filter(|bid| start_after.map_or(true, |start| bid.index < start))
+                .take(limit)
+                .collect(),
+        };
+        Ok(res)
+    }
+
+    pub fn stats(deps: Deps) -> StdResult<response::Stats> {
+        let stats = STATS.may_load(deps.storage)?.unwrap_or_default();
+        let average_bids = match stats.created {
+            0 => Decimal::zero(),
+            created => Decimal::from_ratio(stats.bids, created),
+        };
+        let top_sellers = super::seller_stats()
+            .idx
+            .settled
+            .range(deps.storage, None, None, Order::Descending)
+            .take(DEFAULT_LIMIT as usize)
+            .map(|item| {
+                let (seller, stats) = item?;
+                Ok((Addr::unchecked(seller), stats.settled))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok(response::Stats {
+            stats,
+            average_bids,
+            top_sellers,
+        })
+    }
+
+    pub fn seller_stats(deps: Deps, address: String) -> StdResult<response::SellerStats> {
+        let address = deps.api.addr_validate(&address)?;
+        let stats = super::seller_stats()
+            .may_load(deps.storage, address.as_str())?
+            .unwrap_or_default();
+        Ok(response::SellerStats { address, stats })
+    }
+
+    fn auction_response(
+        deps: Deps,
+        now: u64,
+        id: u64,
+        auction: Auction,
+    ) -> StdResult<response::Auction> {
+        let mut top_bid: Option<&Bid> = None;
+        let mut unique_bidders: Vec<&str> = vec![];
+        for bid in auction.bidders.iter() {
+            if top_bid.map_or(true, |top| bid.value > top.value) {
+                top_bid = Some(bid);
+            }
+            if !unique_bidders.contains(&bid.bidder.as_str()) {
+                unique_bidders.push(bid.bidder.as_str());
+            }
         }
+        let top_bid = top_bid.cloned();
+        let unique_bidders = unique_bidders.len() as u32;
+
+        Ok(response::Auction {
+            id,
+            status: auction.status(now),
+            end_time: auction.end_timestamp(),
+            candle_end_time: auction.candle_end_time,
+            settled: auction.is_candle_blow,
+            name: auction.name,
+            start_timestamp: auction.start_timestmap,
+            auction_duration: auction.auction_duration,
+            top_bid,
+            unique_bidders,
+            curr_winner: auction.curr_winner,
+            lots: auction.lots,
+            lots_expected: auction.lots_expected,
+            eligibility: auction.eligibility,
+            metadata: auction.metadata,
+            lot_metadata: auction.lot_metadata,
+            seller: deps.api.addr_humanize(&auction.seller)?,
+            payment_type: auction.payment_type,
+            payment: auction.payment,
+            accepted_payments: auction.accepted_payments,
+            min_price: auction.min_price,
+            bid_num: auction.bid_num,
+            mode: auction.mode,
+            winners: auction.winners,
+        })
     }
 } //}}}
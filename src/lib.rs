@@ -1,6 +1,8 @@
 pub mod contract;
 pub mod error;
 pub mod handler;
+mod migration;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod mock;
 pub mod msg;
 mod state;
@@ -0,0 +1,189 @@
+use crate::handler::update_stats;
+use crate::state::*;
+use cosmwasm_std::{Addr, DepsMut, Empty, Order, StdResult};
+use semver::Version;
+
+/// State upgrade from the previous layout to the layout of `version`
+struct MigrationStep {
+    version: &'static str,
+    migrate: fn(DepsMut) -> StdResult<()>,
+}
+
+/// Steps in version order. A new step is added whenever a release
+/// changes the layout of the stored state.
+const MIGRATIONS: &[MigrationStep] = &[MigrationStep {
+    version: "0.2.0",
+    migrate: migrate_v0_2,
+}];
+
+/// Run the steps of the versions in `(from, to]`, returns the applied versions
+pub fn migrate_state(mut deps: DepsMut, from: &Version, to: &Version) -> StdResult<Vec<String>> {
+    let mut applied = vec![];
+    for step in MIGRATIONS {
+        let version = Version::parse(step.version).expect("invalid migration version");
+        if version > *from && version <= *to {
+            (step.migrate)(deps.branch())?;
+            applied.push(step.version.to_string());
+        }
+    }
+    Ok(applied)
+}
+
+/// 0.1 layouts
+pub mod v0_1 {
+    use cosmwasm_std::CanonicalAddr;
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
+
+    use crate::state::PaymentType;
+
+    pub const CONFIG: Item<Config> = Item::new("CONFIG");
+    pub const AUCTIONS: Map<u64, Auction> = Map::new("AUCTIONS");
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct Config {
+        pub auction_num: u64,
+        pub min_auction_duration: u64,
+        pub max_auction_duration: u64,
+        pub enable_auction: bool,
+        pub fee_rate: u64,
+        pub default_denom: String,
+        pub support_contract: Vec<String>,
+        pub version: ContractVersion,
+        pub owner: CanonicalAddr,
+        pub oracle_contract: CanonicalAddr,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+    pub struct ContractVersion {
+        pub contract: String,
+        pub version: String,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub struct Auction {
+        pub name: String,
+        pub start_timestmap: u64,
+        pub auction_duration: u64,
+        /// (bidder_address, bid_timestamp, bid_price)
+        pub bidders: Vec<(String, u64, u128)>,
+        pub curr_winner: Option<(String, u64, u128)>,
+        /// (cw721 contract, token id)
+        pub tokens: Vec<(String, String)>,
+        pub seller: CanonicalAddr,
+        pub payment_type: PaymentType,
+        pub payment: String,
+        pub min_price: Option<u128>,
+        pub bid_num: u32,
+        pub is_candle_blow: bool,
+    }
+}
+
+/// 0.2 moves the supported contracts to `COLLECTIONS`, stores the lots and
+/// bids as `Lot`/`Bid`, and builds the auction indexes and statistics.
+fn migrate_v0_2(deps: DepsMut) -> StdResult<()> {
+    let legacy = v0_1::CONFIG.load(deps.storage)?;
+    for contract in legacy.support_contract.iter() {
+        if !COLLECTIONS.has(deps.storage, contract) {
+            COLLECTIONS.save(deps.storage, contract, &CollectionInfo::default())?;
+        }
+    }
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            auction_num: legacy.auction_num,
+            min_auction_duration: legacy.min_auction_duration,
+            max_auction_duration: legacy.max_auction_duration,
+            enable_auction: legacy.enable_auction,
+            fee_rate: legacy.fee_rate,
+            default_denom: legacy.default_denom,
+            owner: legacy.owner,
+            oracle_contract: legacy.oracle_contract,
+            price_oracle: None,
+            restrict_payments: false,
+            max_royalty: default_max_royalty(),
+            creation_policy: CreationPolicy::Open,
+            keeper_reward: 0,
+        },
+    )?;
+
+    let legacy_auctions = v0_1::AUCTIONS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (id, legacy) in legacy_auctions {
+        let asset = PaymentAsset {
+            payment_type: legacy.payment_type.clone(),
+            payment: legacy.payment.clone(),
+        };
+        // 0.1 bids were paid in the auction payment, at their price
+        let bidders: Vec<Bid> = legacy
+            .bidders
+            .iter()
+            .map(|(bidder, timestamp, price)| Bid {
+                bidder: Addr::unchecked(bidder),
+                payer: None,
+                timestamp: *timestamp,
+                value: *price,
+                asset: asset.clone(),
+                amount: *price,
+                refunded: false,
+            })
+            .collect();
+        let winners = match legacy.is_candle_blow {
+            true => legacy.curr_winner.iter().cloned().collect(),
+            false => vec![],
+        };
+        let auction = Auction {
+            name: legacy.name,
+            start_timestmap: legacy.start_timestmap,
+            auction_duration: legacy.auction_duration,
+            bidders,
+            curr_winner: legacy.curr_winner,
+            lots: legacy
+                .tokens
+                .into_iter()
+                .map(|(contract, token_id)| Lot::Cw721 { contract, token_id })
+                .collect(),
+            seller: legacy.seller,
+            payment_type: legacy.payment_type,
+            payment: legacy.payment,
+            accepted_payments: vec![],
+            min_price: legacy.min_price,
+            bid_num: legacy.bid_num,
+            is_candle_blow: legacy.is_candle_blow,
+            mode: AuctionMode::Single,
+            winners,
+            lots_expected: vec![],
+            candle_end_time: None,
+            eligibility: None,
+            metadata: AuctionMetadata::default(),
+            lot_metadata: vec![],
+        };
+
+        // the legacy record cannot be loaded as the indexed map replaces it
+        v0_1::AUCTIONS.remove(deps.storage, id);
+        auctions().save(deps.storage, id, &auction)?;
+
+        for bid in auction.bidders.iter() {
+            BIDDER_AUCTIONS.save(deps.storage, (bid.bidder.as_str(), id), &Empty {})?;
+        }
+
+        let seller = deps.api.addr_humanize(&auction.seller)?;
+        let settled = auction.winners.first().map(|(_, _, price)| *price);
+        update_stats(deps.storage, seller.as_str(), |stats| {
+            stats.created += 1;
+            stats.bids += auction.bidders.len() as u64;
+            match (auction.is_candle_blow, settled) {
+                (true, Some(price)) => {
+                    stats.settled += 1;
+                    stats.add_volume(&asset, price);
+                }
+                (true, None) => stats.passed += 1,
+                _ => {}
+            }
+        })?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
-use crate::msg::RandQueryMsg;
+use crate::msg::{Cw2981Query, Cw2981QueryMsg, PriceQueryMsg, RandQueryMsg, RoyaltiesInfoResponse};
+use cosmwasm_std::testing::MOCK_CONTRACT_ADDR;
 use cosmwasm_std::{
-    from_binary, to_binary, ContractResult, QuerierResult, SystemError, SystemResult, WasmQuery,
+    from_binary, to_binary, ContractResult, Decimal, Empty, QuerierResult, SystemError,
+    SystemResult, Uint128, WasmQuery,
 };
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use cw721::{Cw721QueryMsg, NftInfoResponse, OwnerOfResponse, TokensResponse};
 
 pub fn mock_query_handle(req: &WasmQuery) -> QuerierResult {
     match req {
@@ -16,6 +20,94 @@ pub fn mock_query_handle(req: &WasmQuery) -> QuerierResult {
                     })
                     .unwrap(),
                 ))
+            } else if contract_addr.eq("price_oracle") {
+                // 1 uatom = 2 ugtb
+                let PriceQueryMsg::Price { base, quote } = from_binary(msg).unwrap();
+                let rate = match (base.as_str(), quote.as_str()) {
+                    ("uatom", "ugtb") => Decimal::from_ratio(2u128, 1u128),
+                    _ => Decimal::one(),
+                };
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&crate::msg::PriceResponse { rate }).unwrap(),
+                ))
+            } else if contract_addr.starts_with("cw20") {
+                let msg: Cw20QueryMsg = from_binary(msg).unwrap();
+
+                let res = match msg {
+                    // only `bob` holds tokens
+                    Cw20QueryMsg::Balance { address } => to_binary(&BalanceResponse {
+                        balance: match address.as_str() {
+                            "bob" => Uint128::new(100),
+                            _ => Uint128::zero(),
+                        },
+                    }),
+                    _ => to_binary(&TokenInfoResponse {
+                        name: "Test Token".to_string(),
+                        symbol: "TEST".to_string(),
+                        decimals: 6,
+                        total_supply: Uint128::new(1_000_000),
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+            } else if contract_addr.starts_with("cw721") {
+                // only `cw721_royalty_addr` implements cw2981, with a 5% royalty
+                if let Ok(Cw2981QueryMsg::Extension {
+                    msg: Cw2981Query::RoyaltyInfo { sale_price, .. },
+                }) = from_binary(msg)
+                {
+                    // `cw721_bad_royalty_addr` answers an invalid recipient
+                    let address = match contract_addr.as_str() {
+                        "cw721_royalty_addr" => "creator",
+                        "cw721_bad_royalty_addr" => "Not An Address",
+                        _ => return SystemResult::Err(SystemError::Unknown {}),
+                    };
+                    return SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&RoyaltiesInfoResponse {
+                            address: address.to_string(),
+                            royalty_amount: sale_price * Decimal::percent(5),
+                        })
+                        .unwrap(),
+                    ));
+                }
+
+                let token_id = match from_binary(msg).unwrap() {
+                    Cw721QueryMsg::OwnerOf { token_id, .. } => token_id,
+                    // alice holds a token of every collection
+                    Cw721QueryMsg::Tokens { owner, .. } => {
+                        let tokens = match owner.as_str() {
+                            "alice" => vec!["token_1".to_string()],
+                            _ => vec![],
+                        };
+                        return SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&TokensResponse { tokens }).unwrap(),
+                        ));
+                    }
+                    Cw721QueryMsg::NftInfo { token_id } => {
+                        return SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&NftInfoResponse {
+                                token_uri: Some(format!("ipfs://{}", token_id)),
+                                extension: Empty {},
+                            })
+                            .unwrap(),
+                        ));
+                    }
+                    _ => return SystemResult::Err(SystemError::Unknown {}),
+                };
+                // tokens named `not_received*` are still owned by the seller
+                let owner = if token_id.starts_with("not_received") {
+                    "alice"
+                } else {
+                    MOCK_CONTRACT_ADDR
+                };
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&OwnerOfResponse {
+                        owner: owner.to_string(),
+                        approvals: vec![],
+                    })
+                    .unwrap(),
+                ))
             } else {
                 SystemResult::Err(SystemError::Unknown {})
             }
@@ -1,6 +1,11 @@
-use crate::state::{AuctionStatus, PaymentType};
+pub use crate::state::{
+    AuctionMetadata, AuctionMode, AuctionStats, AuctionStatus, Bid, BidderEligibility,
+    CreationPolicy, Lot, LotMetadata, PauseInfo, PauseScope, PaymentAsset, PaymentType, Role,
+    Royalty,
+};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
+use cw1155::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
 use cw20::Cw20ReceiveMsg;
 use cw721::Cw721ReceiveMsg;
 use schemars::JsonSchema;
@@ -12,10 +17,18 @@ pub struct InstantiateMsg {
     pub min_auction_duration: u64,
     pub max_auction_duration: u64,
     pub enable_auction: bool,
+    /// Protocol fee, in percent of the winning payments
     pub fee_rate: u64,
     pub default_denom: String,
+    /// Initially allowed lot collections
     pub support_contract: Vec<String>,
     pub oracle_contract: String,
+    /// Price oracle valuing bids paid with other accepted payments
+    pub price_oracle: Option<String>,
+    /// Payments auctions are restricted to, any valid payment if not set
+    pub payment_allowlist: Option<Vec<PaymentAsset>>,
+    /// Max royalty of a lot as a share of its price, 10% if not set
+    pub max_royalty: Option<Decimal>,
 }
 
 /// Auction warrper message
@@ -25,11 +38,42 @@ pub struct InstantiateMsg {
 pub struct Auction {
     pub id: u64,
     pub bidder: Option<String>,
+    /// Merkle proof of the bidder for `BidderEligibility::MerkleRoot`
+    pub proof: Option<Vec<String>>,
+}
+
+/// cw20 receive hook messages other than the bid `Auction` message
+#[cw_serde]
+pub enum Cw20HookMsg {
+    /// Deposit the received tokens as a lot of a not started auction
+    DepositLot { auction_id: u64 },
+}
+
+/// cw721 receive hook messages other than the deposit `Auction` message
+#[cw_serde]
+pub enum Cw721HookMsg {
+    /// Create an auction selling the received token in one transaction
+    CreateAuction {
+        name: String,
+        start_timestamp: u64,
+        duration: u64,
+        payment_type: PaymentType,
+        payment: String,
+        min_price: Option<u128>,
+        mode: Option<AuctionMode>,
+        accepted_payments: Option<Vec<PaymentAsset>>,
+        lots_expected: Option<Vec<Lot>>,
+        /// Who can bid, anyone by default
+        eligibility: Option<BidderEligibility>,
+        /// Description, image, tags and external link
+        metadata: Option<AuctionMetadata>,
+    },
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
     // /// Post a new auction
+    /// Attached native coins are auctioned as lots
     Auction {
         name: String,
         start_timestamp: u64,
@@ -37,11 +81,25 @@ pub enum ExecuteMsg {
         payment_type: PaymentType,
         payment: String,
         min_price: Option<u128>,
+        /// Lots distribution mode, default single winner
+        mode: Option<AuctionMode>,
+        /// Other accepted payments, valued in `payment` by the price oracle
+        accepted_payments: Option<Vec<PaymentAsset>>,
+        /// Lots to deposit before the start, any lot if not set
+        lots_expected: Option<Vec<Lot>>,
+        /// Who can bid, anyone by default
+        eligibility: Option<BidderEligibility>,
+        /// Description, image, tags and external link
+        metadata: Option<AuctionMetadata>,
     },
-    /// Winner claim the reward
-    WinnerClaim {
+    /// Edit an auction, by the seller before the start and any bid
+    UpdateAuction {
         auction_id: u64,
-        winner: Option<String>,
+        name: Option<String>,
+        start_timestamp: Option<u64>,
+        duration: Option<u64>,
+        min_price: Option<u128>,
+        metadata: Option<AuctionMetadata>,
     },
     /// Update Config
     UpdateConfig {
@@ -50,35 +108,167 @@ pub enum ExecuteMsg {
         enable_auction: Option<bool>,
         fee_rate: Option<u64>,
         default_denom: Option<String>,
-        support_contract: Option<Vec<String>>,
+        price_oracle: Option<String>,
+        restrict_payments: Option<bool>,
+        max_royalty: Option<Decimal>,
+        /// Percent of the settlement protocol fee paid to `SettleBatch` keepers
+        keeper_reward: Option<u64>,
+    },
+    /// Grant a role, by the owner
+    GrantRole { address: String, role: Role },
+    /// Revoke a role, by the owner
+    RevokeRole { address: String, role: Role },
+    /// Update who can create auctions, by the operator
+    UpdateCreationPolicy { policy: CreationPolicy },
+    /// Allow sellers for `CreationPolicy::Allowlist`, by the operator
+    AddSellers { sellers: Vec<String> },
+    /// Remove allowed sellers, by the operator
+    RemoveSellers { sellers: Vec<String> },
+    /// Update the protocol fee rate, by the fee manager
+    UpdateFeeRate { fee_rate: u64 },
+    /// Withdraw the collected protocol fees of a payment, by the fee manager.
+    /// Withdraw all of them to the sender by default
+    WithdrawFees {
+        payment: String,
+        amount: Option<Uint128>,
+        recipient: Option<String>,
+    },
+    /// Pause operations, by the guardian
+    Pause {
+        scopes: Vec<PauseScope>,
+        reason: String,
     },
+    /// Resume paused operations, by the owner
+    Unpause { scopes: Vec<PauseScope> },
+    /// Allow the lots of a collection, or update its settings
+    AddCollection {
+        contract: String,
+        royalty: Option<Royalty>,
+        min_duration: Option<u64>,
+    },
+    /// Disallow the lots of a collection
+    RemoveCollection { contract: String },
+    /// Allow a payment asset
+    AddPaymentAsset { asset: PaymentAsset },
+    /// Disallow a payment asset
+    RemovePaymentAsset { payment: String },
     /// Candle blow
     BlowCandle { auction_id: u64 },
-    /// Receive cw20 interface
-    Receive(Cw20ReceiveMsg),
+    /// Receive cw20 or cw1155 interface, both use the `receive` key
+    Receive(ReceiveMsg),
     /// auction flow refund
     FlowRefund { auction_id: u64 },
+    /// Refund the non-winning bids of an ended auction before its settlement, by anyone
+    ClaimRefund { auction_id: u64 },
+    /// Blow or flow up to `limit` ended auctions after `start_after`, by anyone.
+    /// Every auction is settled in its own sub-message, a failing one is skipped.
+    SettleBatch {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Settle one auction of a `SettleBatch` and reward its keeper, only by the contract itself
+    KeeperSettle { auction_id: u64, keeper: String },
+    /// Relist the lots of a passed auction in a new auction, by the seller
+    Relist {
+        auction_id: u64,
+        start_timestamp: u64,
+        duration: u64,
+        /// Keeps the passed auction min price if not set
+        min_price: Option<u128>,
+    },
     /// Bid for denom payment
     BidForDenom {
         bidder: Option<String>,
         auction_id: u64,
+        /// Merkle proof of the bidder for `BidderEligibility::MerkleRoot`
+        proof: Option<Vec<String>>,
     },
     /// cw721 recive
     ReceiveNft(Cw721ReceiveMsg),
+    /// cw1155 batch receive, every token of the batch is a lot
+    BatchReceive(Cw1155BatchReceiveMsg),
+}
+
+/// `receive` hook payload, told apart by its fields
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ReceiveMsg {
+    /// cw20 receive, the hook message is a `Cw20HookMsg`
+    Cw20(Cw20ReceiveMsg),
+    /// cw1155 receive, the hook message is the auction warrper message
+    Cw1155(Cw1155ReceiveMsg),
 }
 
 #[cw_serde]
 pub enum QueryMsg {
     /// Get auction static config
     Config {},
-    /// Get Auction list
+    /// Get Auction list, newest first
     AuctionList {
         status: Option<AuctionStatus>,
-        page: u32,
-        limit: u32,
+        /// Only the settled, or unsettled, auctions
+        settled: Option<bool>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
     },
     /// Get auction by auction id
     Auction { id: u64 },
+    /// Get the auctions of a seller, newest first
+    AuctionsBySeller {
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the auctions selling lots of a collection, newest first
+    AuctionsByCollection {
+        collection: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the auctions paid with a denom/cw20 token, newest first
+    AuctionsByPayment {
+        payment: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the bids of an auction, paged by bid index
+    Bids {
+        auction_id: u64,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<OrderBy>,
+    },
+    /// Get the auctions an address bid on with its participation, newest first
+    BidsByBidder {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Get the sellers allowed by `CreationPolicy::Allowlist`
+    Sellers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Get the roles of an address
+    Roles { address: String },
+    /// Get the collected protocol fees
+    Fees {},
+    /// Get the paused operations
+    PauseState {},
+    /// Get the house statistics
+    Stats {},
+    /// Get the statistics of the auctions of a seller
+    SellerStats { address: String },
+    /// Get allowed lot collections
+    Collections {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Get allowed payment assets
+    PaymentAssets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -93,6 +283,43 @@ pub struct GetResponse {
     pub randomness: Binary,
 }
 
+#[cw_serde]
+pub enum PriceQueryMsg {
+    /// Price of one `base` unit in `quote` units
+    Price { base: String, quote: String },
+}
+
+#[cw_serde]
+pub struct PriceResponse {
+    pub rate: Decimal,
+}
+
+/// cw2981 royalty query, wrapped in the cw721 extension query
+#[cw_serde]
+pub enum Cw2981QueryMsg {
+    Extension { msg: Cw2981Query },
+}
+
+#[cw_serde]
+pub enum Cw2981Query {
+    RoyaltyInfo {
+        token_id: String,
+        sale_price: Uint128,
+    },
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    pub royalty_amount: Uint128,
+}
+
+#[cw_serde]
+pub enum OrderBy {
+    Asc,
+    Desc,
+}
+
 pub mod response {
     use super::*;
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -104,25 +331,123 @@ pub mod response {
         pub enable_auction: bool,
         pub fee_rate: u64,
         pub default_denom: String,
-        pub support_contract: Vec<String>,
+        pub restrict_payments: bool,
+        pub max_royalty: Decimal,
+        pub owner: Addr,
+        /// Randomness oracle drawing the candle end time
+        pub oracle_contract: Addr,
+        pub price_oracle: Option<Addr>,
+        pub creation_policy: CreationPolicy,
+        pub keeper_reward: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct Roles {
+        pub address: Addr,
+        /// The owner holds all the roles
+        pub owner: bool,
+        pub roles: Vec<Role>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct Collection {
+        pub contract: String,
+        pub royalty: Option<Royalty>,
+        pub min_duration: Option<u64>,
     }
 
     #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
     #[serde(rename_all = "snake_case")]
     pub struct Auction {
+        pub id: u64,
+        pub status: AuctionStatus,
+        /// Latest possible end time, start_timestamp + auction_duration
+        pub end_time: u64,
+        /// End time drawn at the candle blow
+        pub candle_end_time: Option<u64>,
+        /// Candle blown, or lots returned to the seller
+        pub settled: bool,
         pub name: String,
         pub start_timestamp: u64,
         pub auction_duration: u64,
-        pub bidders: Vec<(String, u64, u128)>,
+        pub eligibility: Option<BidderEligibility>,
+        pub metadata: AuctionMetadata,
+        pub lot_metadata: Vec<LotMetadata>,
+        /// Highest bid by value, bids are paged with `QueryMsg::Bids`
+        pub top_bid: Option<Bid>,
+        pub unique_bidders: u32,
         pub curr_winner: Option<(String, u64, u128)>,
-        pub tokens: Vec<(String, String)>,
+        pub lots: Vec<Lot>,
+        pub lots_expected: Vec<Lot>,
         pub seller: Addr,
         pub payment_type: PaymentType,
         pub payment: String,
+        pub accepted_payments: Vec<PaymentAsset>,
         pub min_price: Option<u128>,
         pub bid_num: u32,
+        pub mode: AuctionMode,
+        pub winners: Vec<(String, u64, u128)>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct PauseState {
+        pub enable_auction: bool,
+        pub paused: Vec<PauseInfo>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct Stats {
+        pub stats: AuctionStats,
+        /// Bids per created auction
+        pub average_bids: Decimal,
+        /// Sellers with the most settled auctions
+        pub top_sellers: Vec<(Addr, u64)>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct SellerStats {
+        pub address: Addr,
+        pub stats: AuctionStats,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct AuctionBid {
+        /// Position of the bid in the auction, used as `start_after`
+        pub index: u32,
+        pub bid: Bid,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+    #[serde(rename_all = "snake_case")]
+    pub struct BidderAuction {
+        pub auction_id: u64,
+        pub status: AuctionStatus,
+        pub settled: bool,
+        /// Number of bids of the bidder
+        pub bid_num: u32,
+        /// Highest bid of the bidder by value
+        pub highest_bid: Option<Bid>,
+        /// Leading right now, or won once settled
+        pub leading: bool,
+        /// Escrowed by the bids until settlement, as any bid before the
+        /// candle end may win. The part not won is refunded at settlement.
+        pub locked: Vec<(PaymentAsset, Uint128)>,
     }
 }
 
+/// Config values set after the state migration
 #[cw_serde]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    pub min_auction_duration: Option<u64>,
+    pub max_auction_duration: Option<u64>,
+    pub enable_auction: Option<bool>,
+    pub fee_rate: Option<u64>,
+    pub default_denom: Option<String>,
+    pub max_royalty: Option<Decimal>,
+}
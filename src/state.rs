@@ -1,10 +1,155 @@
-use cosmwasm_std::CanonicalAddr;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{
+    Addr, Binary, CanonicalAddr, Decimal, Empty, Order, StdError, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 pub const CONFIG: Item<Config> = Item::new("CONFIG");
-pub const AUCTIONS: Map<u64, Auction> = Map::new("AUCTIONS"); // AUCTIONS record
+pub const COLLECTIONS: Map<&str, CollectionInfo> = Map::new("COLLECTIONS"); // allowed lot contracts
+pub const FEES: Map<&str, (PaymentType, Uint128)> = Map::new("FEES"); // payment -> collected protocol fee
+pub const PAYMENT_ALLOWLIST: Map<&str, PaymentType> = Map::new("PAYMENT_ALLOWLIST"); // denom/cw20 address -> payment type
+pub const BIDDER_AUCTIONS: Map<(&str, u64), Empty> = Map::new("BIDDER_AUCTIONS"); // (bidder, auction id) index of the bids
+pub const STATS: Item<AuctionStats> = Item::new("STATS"); // house statistics
+pub const PAUSED: Map<&str, PauseInfo> = Map::new("PAUSED"); // paused scope -> pause
+pub const ROLES: Map<(&str, &str), Role> = Map::new("ROLES"); // (address, role) granted by the owner
+pub const SELLER_ALLOWLIST: Map<&str, Empty> = Map::new("SELLER_ALLOWLIST"); // sellers allowed by `CreationPolicy::Allowlist`
+
+pub struct SellerStatsIndexes<'a> {
+    /// Ranks the sellers by settled auctions
+    pub settled: MultiIndex<'a, u64, AuctionStats, String>,
+}
+
+impl<'a> IndexList<AuctionStats> for SellerStatsIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AuctionStats>> + '_> {
+        let v: Vec<&dyn Index<AuctionStats>> = vec![&self.settled];
+        Box::new(v.into_iter())
+    }
+}
+
+/// seller address -> statistics of its auctions
+pub fn seller_stats<'a>() -> IndexedMap<'a, &'a str, AuctionStats, SellerStatsIndexes<'a>> {
+    let indexes = SellerStatsIndexes {
+        settled: MultiIndex::new(
+            |_pk, stats| stats.settled,
+            "SELLER_STATS",
+            "SELLER_STATS__settled",
+        ),
+    };
+    IndexedMap::new("SELLER_STATS", indexes)
+}
+
+pub struct AuctionIndexes<'a> {
+    pub seller: MultiIndex<'a, Vec<u8>, Auction, u64>,
+    /// The auction payment, other accepted payments are not indexed
+    pub payment: MultiIndex<'a, String, Auction, u64>,
+    /// 1 once the candle was blown or the auction flowed
+    pub settled: MultiIndex<'a, u8, Auction, u64>,
+    pub collection: CollectionIndex<'a>,
+    /// (settled, end time), the unsettled auctions by end time are the settlement queue
+    pub settle_queue: MultiIndex<'a, (u8, u64), Auction, u64>,
+}
+
+impl<'a> IndexList<Auction> for AuctionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Auction>> + '_> {
+        let v: Vec<&dyn Index<Auction>> = vec![
+            &self.seller,
+            &self.payment,
+            &self.settled,
+            &self.collection,
+            &self.settle_queue,
+        ];
+        Box::new(v.into_iter())
+    }
+}
+
+/// AUCTIONS record
+pub fn auctions<'a>() -> IndexedMap<'a, u64, Auction, AuctionIndexes<'a>> {
+    let indexes = AuctionIndexes {
+        seller: MultiIndex::new(
+            |_pk, auction| auction.seller.to_vec(),
+            "AUCTIONS",
+            "AUCTIONS__seller",
+        ),
+        payment: MultiIndex::new(
+            |_pk, auction| auction.payment.clone(),
+            "AUCTIONS",
+            "AUCTIONS__payment",
+        ),
+        settled: MultiIndex::new(
+            |_pk, auction| auction.is_candle_blow as u8,
+            "AUCTIONS",
+            "AUCTIONS__settled",
+        ),
+        collection: CollectionIndex::new("AUCTIONS__collection"),
+        settle_queue: MultiIndex::new(
+            |_pk, auction| (auction.is_candle_blow as u8, auction.end_timestamp()),
+            "AUCTIONS",
+            "AUCTIONS__settle_queue",
+        ),
+    };
+    IndexedMap::new("AUCTIONS", indexes)
+}
+
+/// Index of the auctions by lot contract.
+/// Unlike a `MultiIndex`, an auction is indexed under every contract of its lots.
+pub struct CollectionIndex<'a> {
+    idx_map: Map<'a, (String, u64), Empty>,
+}
+
+impl<'a> CollectionIndex<'a> {
+    pub const fn new(idx_namespace: &'a str) -> Self {
+        CollectionIndex {
+            idx_map: Map::new(idx_namespace),
+        }
+    }
+
+    fn collections(auction: &Auction) -> Vec<String> {
+        let mut collections: Vec<String> = auction
+            .lots
+            .iter()
+            .filter_map(|lot| match lot {
+                Lot::Native { .. } => None,
+                Lot::Cw20 { contract, .. }
+                | Lot::Cw721 { contract, .. }
+                | Lot::Cw1155 { contract, .. } => Some(contract.clone()),
+            })
+            .collect();
+        collections.sort();
+        collections.dedup();
+        collections
+    }
+
+    /// Ids of the auctions selling lots of the collection
+    pub fn auction_ids<'c>(
+        &self,
+        store: &'c dyn Storage,
+        collection: String,
+        min: Option<Bound<'c, u64>>,
+        max: Option<Bound<'c, u64>>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = StdResult<u64>> + 'c> {
+        self.idx_map.prefix(collection).keys(store, min, max, order)
+    }
+}
+
+impl<'a> Index<Auction> for CollectionIndex<'a> {
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &Auction) -> StdResult<()> {
+        let id = u64::from_vec(pk.to_vec())?;
+        for collection in Self::collections(data) {
+            self.idx_map.save(store, (collection, id), &Empty {})?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &Auction) -> StdResult<()> {
+        let id = u64::from_vec(pk.to_vec())?;
+        for collection in Self::collections(old_data) {
+            self.idx_map.remove(store, (collection, id));
+        }
+        Ok(())
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Config {
@@ -12,24 +157,45 @@ pub struct Config {
     pub min_auction_duration: u64,
     pub max_auction_duration: u64,
     pub enable_auction: bool,
+    /// Protocol fee, in percent of the winning payments
     pub fee_rate: u64,
     pub default_denom: String,
-    pub support_contract: Vec<String>,
-    pub version: ContractVersion,
     pub owner: CanonicalAddr,
     pub oracle_contract: CanonicalAddr,
+    /// Price oracle converting accepted payments into the auction payment
+    #[serde(default)]
+    pub price_oracle: Option<CanonicalAddr>,
+    /// Only the payments of `PAYMENT_ALLOWLIST` can be used by auctions
+    #[serde(default)]
+    pub restrict_payments: bool,
+    /// Max royalty of a lot, as a share of its price
+    #[serde(default = "default_max_royalty")]
+    pub max_royalty: Decimal,
+    /// Who can create auctions
+    #[serde(default)]
+    pub creation_policy: CreationPolicy,
+    /// Percent of the protocol fee of a `SettleBatch` paid to the keeper
+    #[serde(default)]
+    pub keeper_reward: u64,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug)]
-pub struct ContractVersion {
-    /// contract is the crate name of the implementing contract, eg. `crate:cw20-base`
-    /// we will use other prefixes for other languages, and their standard global namespacing
-    pub contract: String,
-    /// version is any string that this implementation knows. It may be simple counter "1", "2".
-    /// or semantic version on release tags "v0.7.0", or some custom feature flag list.
-    /// the only code that needs to understand the version parsing is code that knows how to
-    /// migrate from the given contract (and is tied to it's implementation somehow)
-    pub version: String,
+pub fn default_max_royalty() -> Decimal {
+    Decimal::percent(10)
+}
+
+impl Config {
+    pub fn validate(&self) -> StdResult<()> {
+        if self.fee_rate > 100 {
+            return Err(StdError::generic_err("fee_rate is a percent, max 100"));
+        }
+        if self.max_royalty > Decimal::one() {
+            return Err(StdError::generic_err("max_royalty greater than 1"));
+        }
+        if self.keeper_reward > 100 {
+            return Err(StdError::generic_err("keeper_reward is a percent, max 100"));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -39,6 +205,245 @@ pub enum PaymentType {
     Cw20 = 1,
 }
 
+/// Bidders allowed to bid on an auction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BidderEligibility {
+    /// The listed addresses
+    Allowlist { addresses: Vec<String> },
+    /// The holders of a token of the cw721 collection
+    Cw721Holders { collection: String },
+    /// The holders of at least `min_balance` cw20 tokens
+    Cw20Holders { token: String, min_balance: Uint128 },
+    /// The addresses of a keccak256 Merkle tree, proven by the bid.
+    /// Leaves are the hashed addresses, pairs are hashed sorted. Hex encoded.
+    MerkleRoot { root: String },
+}
+
+/// Listing details shown by the frontends
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct AuctionMetadata {
+    pub description: Option<String>,
+    /// Image URI
+    pub image: Option<String>,
+    /// Category tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub external_url: Option<String>,
+}
+
+/// cw721 lot metadata captured at deposit
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct LotMetadata {
+    pub contract: String,
+    pub token_id: String,
+    pub token_uri: Option<String>,
+    /// The raw `NftInfo` response, with the token extension
+    pub nft_info: Binary,
+}
+
+/// Sellers allowed to create auctions
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CreationPolicy {
+    /// Anyone
+    Open,
+    /// The sellers of `SELLER_ALLOWLIST`
+    Allowlist,
+    /// The holders of a token of the cw721 collection
+    NftHolders { collection: String },
+}
+
+impl Default for CreationPolicy {
+    fn default() -> Self {
+        CreationPolicy::Open
+    }
+}
+
+/// Administrative roles granted by the owner, who holds all of them
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Manages the collection and payment allowlists
+    Operator,
+    /// Pauses operations
+    Guardian,
+    /// Changes the fee rate and withdraws the protocol fees
+    FeeManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Operator => "operator",
+            Role::Guardian => "guardian",
+            Role::FeeManager => "fee_manager",
+        }
+    }
+}
+
+/// Operations that can be paused independently.
+/// Returning the lots of passed or cancelled auctions is never paused.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseScope {
+    /// Auction creation
+    Creation,
+    /// Bids of both bid paths
+    Bidding,
+    /// Candle blow, paying the winners, the seller and the refunds
+    Settlement,
+    /// Refund claims of the non-winning bids, available while settlement is paused
+    Refunds,
+}
+
+impl PauseScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PauseScope::Creation => "creation",
+            PauseScope::Bidding => "bidding",
+            PauseScope::Settlement => "settlement",
+            PauseScope::Refunds => "refunds",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PauseInfo {
+    pub scope: PauseScope,
+    pub reason: String,
+    pub paused_by: String,
+    pub paused_at: u64,
+}
+
+/// Running counters of the auctions, for the house or a seller
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct AuctionStats {
+    pub created: u64,
+    /// Candle blown with a winner
+    pub settled: u64,
+    /// Ended without bids
+    pub passed: u64,
+    /// Cancelled as the lots were not deposited
+    pub cancelled: u64,
+    pub bids: u64,
+    /// Paid by the winners per payment asset
+    pub volume: Vec<(PaymentAsset, Uint128)>,
+}
+
+impl AuctionStats {
+    pub fn add_volume(&mut self, asset: &PaymentAsset, amount: u128) {
+        match self.volume.iter_mut().find(|(a, _)| a.eq(asset)) {
+            Some((_, total)) => *total += Uint128::new(amount),
+            None => self.volume.push((asset.clone(), Uint128::new(amount))),
+        }
+    }
+}
+
+/// Settings of an allowed lot collection
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionInfo {
+    /// Royalty paid on settlement when the collection does not implement cw2981
+    pub royalty: Option<Royalty>,
+    /// Min auction duration of the auctions selling the collection lots
+    pub min_duration: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Royalty {
+    pub recipient: String,
+    /// Share of the sale price, in [0, 1]
+    pub share: Decimal,
+}
+
+/// A bid payment asset
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PaymentAsset {
+    pub payment_type: PaymentType,
+    /// denom/cw20 token address
+    pub payment: String,
+}
+
+/// A bid record
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Bid {
+    /// Beneficiary of the bid, receives the won lots
+    pub bidder: Addr,
+    /// Sender of the bid funds, receives the refunds. `None` when the bidder paid.
+    #[serde(default)]
+    pub payer: Option<Addr>,
+    pub timestamp: u64,
+    /// Bid value in the auction payment
+    pub value: u128,
+    /// Asset the bid was paid with
+    pub asset: PaymentAsset,
+    /// Amount paid in `asset`
+    pub amount: u128,
+    /// Refunded through `ClaimRefund` before the settlement
+    #[serde(default)]
+    pub refunded: bool,
+}
+
+impl Bid {
+    /// Recipient of the bid refunds
+    pub fn payer(&self) -> &Addr {
+        self.payer.as_ref().unwrap_or(&self.bidder)
+    }
+}
+
+/// An asset deposited into an auction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Lot {
+    /// Native bank coins attached to the auction creation
+    Native { denom: String, amount: Uint128 },
+    /// cw20 tokens deposited through the `DepositLot` hook
+    Cw20 { contract: String, amount: Uint128 },
+    /// NFT (or DNS) deposited through `ReceiveNft`
+    Cw721 { contract: String, token_id: String },
+    /// Semi-fungible tokens deposited through `Receive1155` / `BatchReceive`
+    Cw1155 {
+        contract: String,
+        token_id: String,
+        amount: Uint128,
+    },
+}
+
+/// How the auction lots are distributed among bidders
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionMode {
+    /// All lots go to the single candle winner
+    Single,
+    /// Every lot is an identical item. The top-N bids before the candle end
+    /// each win one lot, N being the number of deposited lots.
+    Batch { pricing: BatchPricing },
+}
+
+impl Default for AuctionMode {
+    fn default() -> Self {
+        AuctionMode::Single
+    }
+}
+
+/// Price paid by the winners of a batch auction
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchPricing {
+    /// Every winner pays the lowest winning bid, the rest is refunded
+    Uniform,
+    /// Every winner pays his own bid
+    PayAsBid,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct Auction {
@@ -49,25 +454,49 @@ pub struct Auction {
     /// End second-level timestamp to bid, end_timestamp = start_timestmap + auction_duration
     pub auction_duration: u64,
     /// Bidder infomation
-    pub bidders: Vec<(String, u64, u128)>,
+    pub bidders: Vec<Bid>,
     /// Current winner (with bid) who finally won Candle auction.
     /// (bidder_address, bid_timestamp, bid_price)
     pub curr_winner: Option<(String, u64, u128)>,
-    /// ERC721 contract
-    /// rewarding contract address (NFT or DNS), token id
-    pub tokens: Vec<(String, String)>,
+    /// Auctioned lots held by the contract
+    pub lots: Vec<Lot>,
     /// Seller
     pub seller: CanonicalAddr,
     /// bid payment type.
     pub payment_type: PaymentType,
     /// bid payment value. denom/cw20 token address
     pub payment: String,
+    /// Other accepted payments, valued in `payment` by the price oracle
+    #[serde(default)]
+    pub accepted_payments: Vec<PaymentAsset>,
     /// Bid min price
     pub min_price: Option<u128>,
     /// bid num
     pub bid_num: u32,
     /// Auction candle has been blowed
     pub is_candle_blow: bool,
+    /// Lots distribution mode
+    #[serde(default)]
+    pub mode: AuctionMode,
+    /// Winning bids after the candle blow
+    /// (bidder_address, bid_timestamp, paid_price)
+    #[serde(default)]
+    pub winners: Vec<(String, u64, u128)>,
+    /// Lots declared at creation, all of them must be deposited before the start
+    #[serde(default)]
+    pub lots_expected: Vec<Lot>,
+    /// Candle end time drawn at the candle blow
+    #[serde(default)]
+    pub candle_end_time: Option<u64>,
+    /// Who can bid, anyone if none
+    #[serde(default)]
+    pub eligibility: Option<BidderEligibility>,
+    /// Listing details
+    #[serde(default)]
+    pub metadata: AuctionMetadata,
+    /// Metadata of the cw721 lots at their deposit
+    #[serde(default)]
+    pub lot_metadata: Vec<LotMetadata>,
 }
 
 impl Auction {
@@ -80,23 +509,105 @@ impl Auction {
             return AuctionStatus::NotStarted;
         }
 
-        if curr_timestamp
-            > self
-                .start_timestmap
-                .checked_add(self.auction_duration)
-                .unwrap_or(u64::MAX)
-        {
+        if curr_timestamp > self.end_timestamp() {
             return AuctionStatus::Ended;
         }
 
         AuctionStatus::OpeningPeriod
     }
 
+    pub fn payment_asset(&self) -> PaymentAsset {
+        PaymentAsset {
+            payment_type: self.payment_type.clone(),
+            payment: self.payment.clone(),
+        }
+    }
+
+    /// Whether bids can be paid with the given asset
+    pub fn accepts(&self, asset: &PaymentAsset) -> bool {
+        self.payment_asset().eq(asset) || self.accepted_payments.contains(asset)
+    }
+
+    /// Whether the lot can be deposited: any lot if none was declared,
+    /// otherwise a declared lot not deposited yet
+    pub fn expects(&self, lot: &Lot) -> bool {
+        if self.lots_expected.is_empty() {
+            return true;
+        }
+        let count = |lots: &Vec<Lot>| lots.iter().filter(|l| (*l).eq(lot)).count();
+        count(&self.lots_expected) > count(&self.lots)
+    }
+
+    /// The auction has lots and every declared lot was deposited
+    pub fn lots_ready(&self) -> bool {
+        !self.lots.is_empty()
+            && self.lots_expected.iter().all(|lot| {
+                let count = |lots: &Vec<Lot>| lots.iter().filter(|l| (*l).eq(lot)).count();
+                count(&self.lots) >= count(&self.lots_expected)
+            })
+    }
+
+    /// Record a bid, the latest bid is the current winner
+    pub fn place_bid(&mut self, bid: Bid) {
+        self.bid_num += 1;
+        self.curr_winner = Some((bid.bidder.to_string(), bid.timestamp, bid.value));
+        self.bidders.push(bid);
+    }
+
+    pub fn end_timestamp(&self) -> u64 {
+        self.start_timestmap.saturating_add(self.auction_duration)
+    }
+
+    /// Lowest acceptable price for the next bid.
+    /// A batch auction only requires to outbid the lowest of the top-N bids.
     pub fn bid_min_price(&self) -> u128 {
-        if let Some((_, _, amt)) = self.curr_winner {
-            u128::max(amt, self.min_price.unwrap_or(0u128))
-        } else {
-            self.min_price.unwrap_or(0u128)
+        let floor = self.min_price.unwrap_or(0u128);
+        match self.mode {
+            AuctionMode::Single => {
+                if let Some((_, _, amt)) = self.curr_winner {
+                    u128::max(amt, floor)
+                } else {
+                    floor
+                }
+            }
+            AuctionMode::Batch { .. } => {
+                let lots = self.lots.len();
+                if lots == 0 || self.bidders.len() < lots {
+                    return floor;
+                }
+                let mut amounts: Vec<u128> = self.bidders.iter().map(|b| b.value).collect();
+                amounts.sort_unstable_by(|a, b| b.cmp(a));
+                u128::max(amounts[lots - 1], floor)
+            }
+        }
+    }
+
+    /// Pick the winning bids placed before the candle end time.
+    /// Returns (bid index, price value to pay) ordered by rank.
+    pub fn candle_winners(&self, end_time: u64) -> Vec<(usize, u128)> {
+        let mut candidates: Vec<(usize, u128)> = self
+            .bidders
+            .iter()
+            .enumerate()
+            .filter(|(_, bid)| bid.timestamp <= end_time)
+            .map(|(idx, bid)| (idx, bid.value))
+            .collect();
+
+        match &self.mode {
+            AuctionMode::Single => candidates.pop().into_iter().collect(),
+            AuctionMode::Batch { pricing } => {
+                // highest bid first, the earlier bid wins a tie
+                candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+                candidates.truncate(self.lots.len());
+                if let (BatchPricing::Uniform, Some(&(_, clearing_price))) =
+                    (pricing, candidates.last())
+                {
+                    for winner in candidates.iter_mut() {
+                        winner.1 = clearing_price;
+                    }
+                }
+                candidates
+            }
         }
     }
 }
@@ -1,15 +1,23 @@
 mod tests {
-    use crate::contract::{execute, query};
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use crate::contract::{execute, query, reply};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MOCK_CONTRACT_ADDR};
     use cosmwasm_std::{from_binary, Attribute};
 
     use crate::msg::*;
 
     use crate::contract::instantiate;
-    use crate::state::PaymentType;
-    use cosmwasm_std::{coins, to_binary, Addr, CosmosMsg, Timestamp, Uint128, WasmMsg};
+    use crate::error::ContractError;
+    use crate::state::{
+        AuctionMetadata, AuctionMode, AuctionStatus, BatchPricing, BidderEligibility,
+        CreationPolicy, Lot, PauseScope, PaymentAsset, PaymentType, Role, Royalty,
+    };
+    use cosmwasm_std::{
+        coin, coins, to_binary, Addr, BankMsg, CosmosMsg, Decimal, DepsMut, Event, Reply, SubMsg,
+        SubMsgResult, Timestamp, Uint128, WasmMsg,
+    };
     use cw721::{Cw721ExecuteMsg, Cw721ReceiveMsg};
     const TEST_DENOM: &str = "ugtb";
+    use cw1155::{Cw1155BatchReceiveMsg, Cw1155ExecuteMsg, Cw1155ReceiveMsg};
     use cw20::Cw20ReceiveMsg;
 
     #[test]
@@ -23,12 +31,21 @@ mod tests {
             default_denom: TEST_DENOM.to_string(),
             support_contract: vec!["cw20_contract_addr1".to_string()],
             oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
         };
 
         let info = mock_info("admin", &coins(0, TEST_DENOM.to_string()));
         let res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
         assert_eq!(0, res.messages.len());
         assert_eq!(2, res.attributes.len());
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: response::Config = from_binary(&res).unwrap();
+        assert_eq!(config.owner, Addr::unchecked("admin"));
+        assert_eq!(config.oracle_contract, Addr::unchecked("oracle_contract"));
+        assert_eq!(config.price_oracle, None);
     }
 
     #[test]
@@ -36,27 +53,48 @@ mod tests {
         let mut deps = mock_dependencies();
         let msg = InstantiateMsg {
             min_auction_duration: 0,
-            max_auction_duration: 2 * 30 * 3600,
+            max_auction_duration: 2 * 30 * 24 * 3600,
             enable_auction: true,
             fee_rate: 2,
             default_denom: TEST_DENOM.to_string(),
             support_contract: vec!["cw20_contract_addr1".to_string()],
             oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
         };
 
         let info = mock_info("admin", &coins(0, TEST_DENOM.to_string()));
         instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
 
-        let auction_msg = ExecuteMsg::Auction {
+        let auction_msg = |duration: u64| ExecuteMsg::Auction {
             name: "test_auction_1".to_string(),
             start_timestamp: 1664805457,
-            duration: 2 * 30 * 24 * 3600,
-            payment_type: PaymentType::Cw20,
+            duration,
+            payment_type: PaymentType::Coin,
             payment: "ugtb".to_string(),
             min_price: Some(123),
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
         };
 
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), auction_msg).unwrap();
+        // The duration stays within the config bounds
+        let msg = auction_msg(2 * 30 * 24 * 3600 + 1);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidDuration {
+                duration: 2 * 30 * 24 * 3600 + 1,
+                min: 0,
+                max: 2 * 30 * 24 * 3600
+            }
+        );
+
+        let msg = auction_msg(2 * 30 * 24 * 3600);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         let attris = vec![
             Attribute {
                 key: "method".to_string(),
@@ -96,6 +134,7 @@ mod tests {
     #[test]
     fn test_recv_cw20() {
         let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
         let msg = InstantiateMsg {
             min_auction_duration: 0,
             max_auction_duration: 2 * 24 * 30 * 3600,
@@ -104,6 +143,9 @@ mod tests {
             default_denom: TEST_DENOM.to_string(),
             support_contract: vec!["cw20_contract_addr1".to_string()],
             oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
         };
 
         let mut info = mock_info("admin", &coins(0, TEST_DENOM.to_string()));
@@ -116,14 +158,22 @@ mod tests {
             payment_type: PaymentType::Cw20,
             payment: "cw20_contract_addr1".to_string(),
             min_price: Some(123),
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
         };
 
-        execute(deps.as_mut(), mock_env(), info.clone(), post_auction_msg).unwrap();
+        // a native lot
+        let seller = mock_info("admin", &coins(1, "uatom"));
+        execute(deps.as_mut(), mock_env(), seller, post_auction_msg).unwrap();
 
         // First auction bid success
         let auction_msg = Auction {
             id: 0,
             bidder: None,
+            proof: None,
         };
 
         let token_msg = Cw20ReceiveMsg {
@@ -138,7 +188,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            ExecuteMsg::Receive(token_msg),
+            ExecuteMsg::Receive(ReceiveMsg::Cw20(token_msg)),
         )
         .unwrap();
         assert_eq!(res.attributes.len(), 1, "res.attributes is not expect");
@@ -147,6 +197,7 @@ mod tests {
         let auction_msg = Auction {
             id: 0,
             bidder: None,
+            proof: None,
         };
 
         let token_msg = Cw20ReceiveMsg {
@@ -159,7 +210,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            ExecuteMsg::Receive(token_msg),
+            ExecuteMsg::Receive(ReceiveMsg::Cw20(token_msg)),
         )
         .unwrap();
 
@@ -186,6 +237,9 @@ mod tests {
                 "cw721_contract_addr".to_string(),
             ],
             oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
         };
 
         let mut info = mock_info("alice", &coins(0, TEST_DENOM.to_string()));
@@ -198,6 +252,11 @@ mod tests {
             payment_type: PaymentType::Cw20,
             payment: "cw20_contract_addr1".to_string(),
             min_price: Some(123),
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
         };
 
         execute(deps.as_mut(), mock_env(), info.clone(), post_auction_msg).unwrap();
@@ -208,8 +267,9 @@ mod tests {
             mock_env(),
             QueryMsg::AuctionList {
                 status: None,
-                page: 0,
-                limit: 10,
+                settled: None,
+                start_after: None,
+                limit: Some(10),
             },
         )
         .unwrap();
@@ -219,6 +279,7 @@ mod tests {
         let auction_msg = Auction {
             id: 0,
             bidder: None,
+            proof: None,
         };
 
         let token_msg = Cw721ReceiveMsg {
@@ -244,6 +305,7 @@ mod tests {
         let auction_msg = Auction {
             id: 0,
             bidder: None,
+            proof: None,
         };
 
         info.sender = Addr::unchecked("cw20_contract_addr1");
@@ -258,7 +320,7 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            ExecuteMsg::Receive(token_msg1),
+            ExecuteMsg::Receive(ReceiveMsg::Cw20(token_msg1)),
         )
         .unwrap();
 
@@ -275,42 +337,27 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             info.clone(),
-            ExecuteMsg::Receive(token_msg2),
+            ExecuteMsg::Receive(ReceiveMsg::Cw20(token_msg2)),
         )
         .unwrap();
         assert!(res.attributes.len() == 1, "attri");
         assert_eq!(res.messages.len(), 0, "yes");
 
-        // Keven reclaim winner tokens
-        let claim_msg = ExecuteMsg::WinnerClaim {
-            auction_id: 0,
-            winner: Some("keven".to_string()),
-        };
-
         let mut end_env = mock_env();
         end_env.block.time = Timestamp::from_seconds(1571797400 + 100000000);
 
-        let res = execute(deps.as_mut(), end_env.clone(), info.clone(), claim_msg).unwrap();
-        assert_eq!(res.attributes.len(), 1, "attri error");
-
-        let transfer_msg: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: "cw721_contract_addr".to_string(),
-            msg: to_binary(&Cw721ExecuteMsg::TransferNft {
-                recipient: "keven".to_string(),
-                token_id: "test_token".to_string(),
-            })
-            .unwrap(),
-            funds: vec![],
-        });
-
-        assert_eq!(res.messages[0].msg, transfer_msg, "made transfer");
-
         let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
 
-        let res = execute(deps.as_mut(), end_env.clone(), info.clone(), blow_candle).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            end_env.clone(),
+            info.clone(),
+            blow_candle.clone(),
+        )
+        .unwrap();
 
         // 1: bidder: bob is not a winner. make refund
-        // 2: seller: auction ended. alice recv the bid amount
+        // 2: seller: auction ended. alice recv the bid amount minus the 2% fee
         // 3: keven: keven is winner. recv the cw721 token.
         let refund_msg_2: CosmosMsg = CosmosMsg::Wasm(WasmMsg::Execute {
             contract_addr: "cw20_contract_addr1".to_string(),
@@ -326,7 +373,7 @@ mod tests {
             contract_addr: "cw20_contract_addr1".to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
                 recipient: "alice".to_string(),
-                amount: Uint128::new(400),
+                amount: Uint128::new(392),
             })
             .unwrap(),
             funds: vec![],
@@ -345,5 +392,2702 @@ mod tests {
         assert_eq!(res.messages[0].msg, refund_msg_2, "refund msg");
         assert_eq!(res.messages[1].msg, recv_token_msg_3, "recv token msg");
         assert_eq!(res.messages[2].msg, cw721_transfer_msg, "cw721 transfer");
+
+        // The candle is blown once
+        let err = execute(deps.as_mut(), end_env.clone(), info.clone(), blow_candle).unwrap_err();
+        assert_eq!(err, ContractError::AuctionAlreadySettled {});
+
+        let res = query(deps.as_ref(), end_env, QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert_eq!(auction.id, 0);
+        assert_eq!(auction.status, AuctionStatus::Ended);
+        assert!(auction.settled);
+        assert!(auction.candle_end_time.unwrap() <= auction.end_time);
+    }
+
+    #[test]
+    fn test_batch_auction() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_contract_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+
+        let mut info = mock_info("alice", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_batch_auction".to_string(),
+            start_timestamp: 1571797400,
+            duration: 2 * 30 * 24 * 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: Some(100),
+            mode: Some(AuctionMode::Batch {
+                pricing: BatchPricing::Uniform,
+            }),
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), post_auction_msg).unwrap();
+
+        // Two identical lots
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797399);
+        info.sender = Addr::unchecked("cw721_contract_addr");
+        for token_id in ["token_1", "token_2"] {
+            let token_msg = Cw721ReceiveMsg {
+                sender: "alice".to_string(),
+                token_id: token_id.to_string(),
+                msg: to_binary(&Auction {
+                    id: 0,
+                    bidder: None,
+                    proof: None,
+                })
+                .unwrap(),
+            };
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                ExecuteMsg::ReceiveNft(token_msg),
+            )
+            .unwrap();
+        }
+
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        for (bidder, amount) in [("bob", 200u128), ("carol", 300), ("dave", 250)] {
+            let info = mock_info(bidder, &coins(amount, TEST_DENOM));
+            execute(deps.as_mut(), mock_env(), info, bid.clone()).unwrap();
+        }
+
+        // Both lots are taken, a bid must beat the lowest of the top two
+        let info = mock_info("erin", &coins(240, TEST_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), bid).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AuctionPriceTooLow {
+                min_price: 250,
+                current: 240
+            }
+        );
+
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797400 + 100000000);
+
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        let res = execute(deps.as_mut(), end_env, info, blow_candle).unwrap();
+
+        // carol and dave win one lot each at the uniform clearing price 250,
+        // alice receives the 500 paid minus the 2% fee
+        let bank = |to: &str, amount: u128| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: to.to_string(),
+                amount: coins(amount, TEST_DENOM),
+            }
+            .into()
+        };
+        let nft = |to: &str, token_id: &str| -> CosmosMsg {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "cw721_contract_addr".to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: to.to_string(),
+                    token_id: token_id.to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        };
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(
+            msgs,
+            vec![
+                bank("carol", 50),
+                bank("bob", 200),
+                bank("alice", 490),
+                nft("carol", "token_1"),
+                nft("dave", "token_2"),
+            ]
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: Option<response::Auction> = from_binary(&res).unwrap();
+        let winners: Vec<(String, u128)> = auction
+            .unwrap()
+            .winners
+            .into_iter()
+            .map(|(bidder, _, price)| (bidder, price))
+            .collect();
+        assert_eq!(
+            winners,
+            vec![("carol".to_string(), 250), ("dave".to_string(), 250)]
+        );
+    }
+
+    #[test]
+    fn test_fungible_lots() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw20_lot_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+
+        let info = mock_info("alice", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        // Native coins attached to the auction creation are lots
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_fungible_lots".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        // Only the seller can deposit cw20 lots
+        let deposit = |sender: &str| {
+            ExecuteMsg::Receive(ReceiveMsg::Cw20(Cw20ReceiveMsg {
+                sender: sender.to_string(),
+                amount: Uint128::new(500),
+                msg: to_binary(&Cw20HookMsg::DepositLot { auction_id: 0 }).unwrap(),
+            }))
+        };
+        let info = mock_info("cw20_lot_addr", &[]);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), deposit("bob")).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NotOwner {
+                sender: "bob".to_string(),
+                owner: "alice".to_string()
+            }
+        );
+        execute(deps.as_mut(), mock_env(), info.clone(), deposit("alice")).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: Option<response::Auction> = from_binary(&res).unwrap();
+        let lots = vec![
+            Lot::Native {
+                denom: "uatom".to_string(),
+                amount: Uint128::new(1000),
+            },
+            Lot::Cw20 {
+                contract: "cw20_lot_addr".to_string(),
+                amount: Uint128::new(500),
+            },
+        ];
+        assert_eq!(auction.unwrap().lots, lots);
+
+        // Nobody bid, the lots return to the seller
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797500 + 3601);
+        let flow = ExecuteMsg::FlowRefund { auction_id: 0 };
+        let res = execute(deps.as_mut(), end_env, info, flow).unwrap();
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(
+            msgs,
+            vec![
+                BankMsg::Send {
+                    to_address: "alice".to_string(),
+                    amount: coins(1000, "uatom"),
+                }
+                .into(),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "cw20_lot_addr".to_string(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: "alice".to_string(),
+                        amount: Uint128::new(500),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cw1155_lots() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw1155_contract_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+
+        let info = mock_info("alice", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_cw1155_lots".to_string(),
+            start_timestamp: 1571797400,
+            duration: 2 * 30 * 24 * 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797399);
+        let info = mock_info("cw1155_contract_addr", &[]);
+        let hook = to_binary(&Auction {
+            id: 0,
+            bidder: None,
+            proof: None,
+        })
+        .unwrap();
+
+        let batch_msg = ExecuteMsg::BatchReceive(Cw1155BatchReceiveMsg {
+            operator: "alice".to_string(),
+            from: Some("alice".to_string()),
+            batch: vec![
+                ("sword".to_string(), Uint128::new(2)),
+                ("shield".to_string(), Uint128::new(1)),
+            ],
+            msg: hook.clone(),
+        });
+        execute(deps.as_mut(), env.clone(), info.clone(), batch_msg).unwrap();
+
+        // cw1155 `SendFrom` shares the `receive` key with cw20
+        let receive_msg = Cw1155ReceiveMsg {
+            operator: "alice".to_string(),
+            from: None,
+            token_id: "potion".to_string(),
+            amount: Uint128::new(5),
+            msg: hook,
+        };
+        let receive_msg: ExecuteMsg = from_binary(&receive_msg.into_binary().unwrap()).unwrap();
+        execute(deps.as_mut(), env, info, receive_msg).unwrap();
+
+        // cw20 payloads are still told apart
+        let cw20_msg = Cw20ReceiveMsg {
+            sender: "bob".to_string(),
+            amount: Uint128::new(5),
+            msg: to_binary(&Cw20HookMsg::DepositLot { auction_id: 0 }).unwrap(),
+        };
+        let cw20_msg: ExecuteMsg = from_binary(&cw20_msg.into_binary().unwrap()).unwrap();
+        assert!(matches!(cw20_msg, ExecuteMsg::Receive(ReceiveMsg::Cw20(_))));
+
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(100, TEST_DENOM));
+        execute(deps.as_mut(), mock_env(), info.clone(), bid).unwrap();
+
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797400 + 100000000);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        let res = execute(deps.as_mut(), end_env.clone(), info, blow_candle).unwrap();
+
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(
+            msgs,
+            vec![
+                BankMsg::Send {
+                    to_address: "alice".to_string(),
+                    amount: coins(98, TEST_DENOM),
+                }
+                .into(),
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: "cw1155_contract_addr".to_string(),
+                    msg: to_binary(&Cw1155ExecuteMsg::BatchSendFrom {
+                        from: end_env.contract.address.to_string(),
+                        to: "bob".to_string(),
+                        batch: vec![
+                            ("sword".to_string(), Uint128::new(2)),
+                            ("shield".to_string(), Uint128::new(1)),
+                            ("potion".to_string(), Uint128::new(5)),
+                        ],
+                        msg: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_payment_bid() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: Some("price_oracle".to_string()),
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+
+        let info = mock_info("alice", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_multi_payment".to_string(),
+            start_timestamp: 1571797400,
+            duration: 2 * 30 * 24 * 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: Some(vec![PaymentAsset {
+                payment_type: PaymentType::Coin,
+                payment: "uatom".to_string(),
+            }]),
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1, "uosmo"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(300, TEST_DENOM));
+        execute(deps.as_mut(), mock_env(), info, bid.clone()).unwrap();
+
+        // 200 uatom is valued 400 ugtb
+        let info = mock_info("carol", &coins(200, "uatom"));
+        let res = execute(deps.as_mut(), mock_env(), info, bid.clone()).unwrap();
+        assert_eq!(
+            res.events,
+            vec![Event::new("bid_placed")
+                .add_attribute("auction_id", "0")
+                .add_attribute("bidder", "carol")
+                .add_attribute("amount", "200")
+                .add_attribute("asset", "uatom")
+                .add_attribute("value", "400")]
+        );
+
+        let info = mock_info("dave", &coins(140, "uatom"));
+        let err = execute(deps.as_mut(), mock_env(), info, bid.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AuctionPriceTooLow {
+                min_price: 400,
+                current: 280
+            }
+        );
+
+        // Unaccepted denom
+        let info = mock_info("dave", &coins(1000, "uosmo"));
+        let err = execute(deps.as_mut(), mock_env(), info, bid.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedPayment {
+                payment: "uosmo".to_string()
+            }
+        );
+
+        // Unaccepted cw20
+        let cw20_bid = Cw20ReceiveMsg {
+            sender: "dave".to_string(),
+            amount: Uint128::new(1000),
+            msg: to_binary(&Auction {
+                id: 0,
+                bidder: None,
+                proof: None,
+            })
+            .unwrap(),
+        };
+        let info = mock_info("cw20_unknown", &[]);
+        let msg = ExecuteMsg::Receive(ReceiveMsg::Cw20(cw20_bid));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedPayment {
+                payment: "cw20_unknown".to_string()
+            }
+        );
+
+        // A bid is a single coin
+        let funds = [coin(1000, TEST_DENOM), coin(1000, "uatom")];
+        let info = mock_info("dave", &funds);
+        execute(deps.as_mut(), mock_env(), info, bid).unwrap_err();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: Option<response::Auction> = from_binary(&res).unwrap();
+        let auction = auction.unwrap();
+        assert_eq!(auction.bid_num, 2);
+        assert_eq!(auction.unique_bidders, 2);
+        let top_bid = auction.top_bid.unwrap();
+        assert_eq!(top_bid.value, 400);
+        assert_eq!(top_bid.amount, 200);
+
+        let msg = QueryMsg::Bids {
+            auction_id: 0,
+            start_after: None,
+            limit: Some(1),
+            order: Some(OrderBy::Desc),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let bids: Vec<response::AuctionBid> = from_binary(&res).unwrap();
+        assert_eq!(bids.len(), 1);
+        assert_eq!(bids[0].index, 1);
+        assert_eq!(bids[0].bid.bidder, "carol");
+
+        let msg = QueryMsg::Bids {
+            auction_id: 0,
+            start_after: Some(0),
+            limit: None,
+            order: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let bids: Vec<response::AuctionBid> = from_binary(&res).unwrap();
+        assert_eq!(bids.len(), 1);
+        assert_eq!(bids[0].index, 1);
+
+        // The seller is paid in the winner asset, minus the 2% fee
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797400 + 100000000);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        let info = mock_info("anyone", &[]);
+        let res = execute(deps.as_mut(), end_env, info, blow_candle).unwrap();
+        let events: Vec<&str> = res.events.iter().map(|e| e.ty.as_str()).collect();
+        assert_eq!(
+            events,
+            vec!["candle_blown", "refund_issued", "auction_settled"]
+        );
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(
+            msgs,
+            vec![
+                BankMsg::Send {
+                    to_address: "bob".to_string(),
+                    amount: coins(300, TEST_DENOM),
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: "alice".to_string(),
+                    amount: coins(196, "uatom"),
+                }
+                .into(),
+                BankMsg::Send {
+                    to_address: "carol".to_string(),
+                    amount: coins(1, "uosmo"),
+                }
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_payment_validation() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: Some(vec![
+                PaymentAsset {
+                    payment_type: PaymentType::Coin,
+                    payment: TEST_DENOM.to_string(),
+                },
+                PaymentAsset {
+                    payment_type: PaymentType::Cw20,
+                    payment: "cw20_contract_addr".to_string(),
+                },
+            ]),
+            max_royalty: None,
+        };
+
+        let info = mock_info("alice", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let auction_msg = |payment_type: PaymentType, payment: &str| ExecuteMsg::Auction {
+            name: "test_payment_validation".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type,
+            payment: payment.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+
+        // Not a cw20 token contract
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            auction_msg(PaymentType::Cw20, "ugtb"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPayment {
+                payment: "ugtb".to_string()
+            }
+        );
+
+        // Malformed denom
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            auction_msg(PaymentType::Coin, "1u"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidPayment {
+                payment: "1u".to_string()
+            }
+        );
+
+        // Valid but not allowed
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            auction_msg(PaymentType::Coin, "uatom"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedPayment {
+                payment: "uatom".to_string()
+            }
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            auction_msg(PaymentType::Coin, TEST_DENOM),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            auction_msg(PaymentType::Cw20, "cw20_contract_addr"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_allowlist_admin() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_contract_a".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        let owner = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+        let add_collection = ExecuteMsg::AddCollection {
+            contract: "cw721_contract_b".to_string(),
+            royalty: None,
+            min_duration: Some(7200),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            add_collection.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::MissingRole {
+                sender: "alice".to_string(),
+                role: "operator".to_string()
+            }
+        );
+        execute(deps.as_mut(), mock_env(), owner.clone(), add_collection).unwrap();
+
+        let collections = |deps: &cosmwasm_std::OwnedDeps<_, _, _>, start_after: Option<&str>| {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Collections {
+                    start_after: start_after.map(|s| s.to_string()),
+                    limit: Some(1),
+                },
+            )
+            .unwrap();
+            from_binary::<Vec<response::Collection>>(&res).unwrap()
+        };
+        let page = collections(&deps, None);
+        assert_eq!(page[0].contract, "cw721_contract_a");
+        let page = collections(&deps, Some("cw721_contract_a"));
+        assert_eq!(page[0].contract, "cw721_contract_b");
+        assert_eq!(page[0].min_duration, Some(7200));
+
+        // The collection requires auctions of two hours at least
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_allowlist".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let alice = mock_info("alice", &[]);
+        execute(deps.as_mut(), mock_env(), alice, post_auction_msg).unwrap();
+
+        let deposit = |token_id: &str| {
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: "alice".to_string(),
+                token_id: token_id.to_string(),
+                msg: to_binary(&Auction {
+                    id: 0,
+                    bidder: None,
+                    proof: None,
+                })
+                .unwrap(),
+            })
+        };
+        let collection_b = mock_info("cw721_contract_b", &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            collection_b.clone(),
+            deposit("b1"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DurationTooShort {
+                input_duration: 3600,
+                min_duration: 7200
+            }
+        );
+
+        let collection_a = mock_info("cw721_contract_a", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            collection_a.clone(),
+            deposit("a1"),
+        )
+        .unwrap();
+
+        let remove_collection = ExecuteMsg::RemoveCollection {
+            contract: "cw721_contract_a".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), owner.clone(), remove_collection).unwrap();
+        execute(deps.as_mut(), mock_env(), collection_a, deposit("a2")).unwrap_err();
+
+        // Payment assets
+        let add_payment = ExecuteMsg::AddPaymentAsset {
+            asset: PaymentAsset {
+                payment_type: PaymentType::Cw20,
+                payment: "cw20_contract_addr".to_string(),
+            },
+        };
+        execute(deps.as_mut(), mock_env(), owner.clone(), add_payment).unwrap();
+        let update_config = ExecuteMsg::UpdateConfig {
+            min_auction_duration: None,
+            max_auction_duration: None,
+            enable_auction: None,
+            fee_rate: None,
+            default_denom: None,
+            price_oracle: None,
+            restrict_payments: Some(true),
+            max_royalty: None,
+            keeper_reward: None,
+        };
+        execute(deps.as_mut(), mock_env(), owner.clone(), update_config).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PaymentAssets {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let assets: Vec<PaymentAsset> = from_binary(&res).unwrap();
+        assert_eq!(assets.len(), 1);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: response::Config = from_binary(&res).unwrap();
+        assert!(config.restrict_payments);
+
+        let remove_payment = ExecuteMsg::RemovePaymentAsset {
+            payment: "cw20_contract_addr".to_string(),
+        };
+        execute(deps.as_mut(), mock_env(), owner, remove_payment).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PaymentAssets {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let assets: Vec<PaymentAsset> = from_binary(&res).unwrap();
+        assert!(assets.is_empty());
+    }
+
+    #[test]
+    fn test_lots_expected() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_contract_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        let info = mock_info("alice", &[]);
+        instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let nft = |token_id: &str| Lot::Cw721 {
+            contract: "cw721_contract_addr".to_string(),
+            token_id: token_id.to_string(),
+        };
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_lots_expected".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: Some(vec![nft("token_1"), nft("not_received_2")]),
+            eligibility: None,
+            metadata: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        let deposit = |token_id: &str| {
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: "alice".to_string(),
+                token_id: token_id.to_string(),
+                msg: to_binary(&Auction {
+                    id: 0,
+                    bidder: None,
+                    proof: None,
+                })
+                .unwrap(),
+            })
+        };
+        let collection = mock_info("cw721_contract_addr", &[]);
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            collection.clone(),
+            deposit("token_3"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::UnexpectedLot {});
+
+        // The contract is not the owner of the token
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            collection.clone(),
+            deposit("not_received_2"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BadRequest {
+                msg: "Token not received".to_string()
+            }
+        );
+
+        execute(deps.as_mut(), mock_env(), collection, deposit("token_1")).unwrap();
+
+        // Started without all its lots: bids are rejected and it can be cancelled
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797600);
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(100, TEST_DENOM));
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), bid).unwrap_err();
+        assert_eq!(err, ContractError::LotsNotDeposited {});
+
+        let flow = ExecuteMsg::FlowRefund { auction_id: 0 };
+        let res = execute(deps.as_mut(), env, info, flow).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "cw721_contract_addr".to_string(),
+                msg: to_binary(&Cw721ExecuteMsg::TransferNft {
+                    recipient: "alice".to_string(),
+                    token_id: "token_1".to_string(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_auction_on_receive_nft() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_contract_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        // The auction starts right away
+        let env = mock_env();
+        let create_msg = Cw721HookMsg::CreateAuction {
+            name: "test_one_shot".to_string(),
+            start_timestamp: env.block.time.seconds(),
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: Some(10),
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let token_msg = Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "test_token".to_string(),
+            msg: to_binary(&create_msg).unwrap(),
+        };
+        let info = mock_info("cw721_contract_addr", &[]);
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::ReceiveNft(token_msg),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&Attribute {
+            key: "auction_id".to_string(),
+            value: "0".to_string(),
+        }));
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert_eq!(auction.seller, Addr::unchecked("alice"));
+        assert_eq!(
+            auction.lots,
+            vec![Lot::Cw721 {
+                contract: "cw721_contract_addr".to_string(),
+                token_id: "test_token".to_string(),
+            }]
+        );
+
+        // Bids are accepted in the same block
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        execute(
+            deps.as_mut(),
+            env,
+            mock_info("bob", &coins(10, TEST_DENOM)),
+            bid,
+        )
+        .unwrap();
+
+        // Unsupported collections cannot create auctions
+        let token_msg = Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "test_token".to_string(),
+            msg: to_binary(&create_msg).unwrap(),
+        };
+        let info = mock_info("cw721_unknown_addr", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            ExecuteMsg::ReceiveNft(token_msg),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_royalties() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_royalty_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: Some(Decimal::percent(10)),
+        };
+        let owner = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+        // No cw2981 support, the collection royalty is configured by the owner
+        let add_collection = ExecuteMsg::AddCollection {
+            contract: "cw721_contract_addr".to_string(),
+            royalty: Some(Royalty {
+                recipient: "studio".to_string(),
+                share: Decimal::percent(20),
+            }),
+            min_duration: None,
+        };
+        execute(deps.as_mut(), mock_env(), owner, add_collection).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_royalties".to_string(),
+            start_timestamp: 1571797400,
+            duration: 2 * 30 * 24 * 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            post_auction_msg,
+        )
+        .unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797399);
+        for collection in ["cw721_royalty_addr", "cw721_contract_addr"] {
+            let token_msg = Cw721ReceiveMsg {
+                sender: "alice".to_string(),
+                token_id: "test_token".to_string(),
+                msg: to_binary(&Auction {
+                    id: 0,
+                    bidder: None,
+                    proof: None,
+                })
+                .unwrap(),
+            };
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                mock_info(collection, &[]),
+                ExecuteMsg::ReceiveNft(token_msg),
+            )
+            .unwrap();
+        }
+
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(1000, TEST_DENOM));
+        execute(deps.as_mut(), mock_env(), info.clone(), bid).unwrap();
+
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797400 + 100000000);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        let res = execute(deps.as_mut(), end_env, info, blow_candle).unwrap();
+
+        // Each lot sells for 500:
+        // - cw2981 royalty of 5%: 25
+        // - collection royalty of 20%, capped at 10%: 50
+        // - protocol fee of 2%: 20
+        let bank = |to: &str, amount: u128| -> CosmosMsg {
+            BankMsg::Send {
+                to_address: to.to_string(),
+                amount: coins(amount, TEST_DENOM),
+            }
+            .into()
+        };
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(
+            msgs[..3],
+            vec![bank("alice", 905), bank("creator", 25), bank("studio", 50)]
+        );
+        assert_eq!(msgs.len(), 5);
+
+        // An invalid cw2981 recipient falls back to the collection royalty
+        let add_collection = ExecuteMsg::AddCollection {
+            contract: "cw721_bad_royalty_addr".to_string(),
+            royalty: Some(Royalty {
+                recipient: "label".to_string(),
+                share: Decimal::percent(5),
+            }),
+            min_duration: None,
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            add_collection,
+        )
+        .unwrap();
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_royalties".to_string(),
+            start_timestamp: 1571797400,
+            duration: 2 * 30 * 24 * 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &[]);
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+        let token_msg = Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "test_token".to_string(),
+            msg: to_binary(&Auction {
+                id: 1,
+                bidder: None,
+                proof: None,
+            })
+            .unwrap(),
+        };
+        let info = mock_info("cw721_bad_royalty_addr", &[]);
+        execute(deps.as_mut(), env, info, ExecuteMsg::ReceiveNft(token_msg)).unwrap();
+
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 1,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(1000, TEST_DENOM));
+        execute(deps.as_mut(), mock_env(), info.clone(), bid).unwrap();
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797400 + 100000000);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 1 };
+        let res = execute(deps.as_mut(), end_env, info, blow_candle).unwrap();
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(msgs[..2], vec![bank("alice", 930), bank("label", 50)]);
+    }
+
+    #[test]
+    fn test_indexed_auction_queries() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![
+                "cw721_contract_addr".to_string(),
+                "cw721_other_addr".to_string(),
+            ],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let create = |deps: DepsMut, seller: &str, collection: &str, payment: &str| {
+            let create_msg = Cw721HookMsg::CreateAuction {
+                name: "test_index".to_string(),
+                start_timestamp: 1571797400,
+                duration: 3600,
+                payment_type: PaymentType::Coin,
+                payment: payment.to_string(),
+                min_price: None,
+                mode: None,
+                accepted_payments: None,
+                lots_expected: None,
+                eligibility: None,
+                metadata: None,
+            };
+            let token_msg = Cw721ReceiveMsg {
+                sender: seller.to_string(),
+                token_id: "test_token".to_string(),
+                msg: to_binary(&create_msg).unwrap(),
+            };
+            execute(
+                deps,
+                mock_env(),
+                mock_info(collection, &[]),
+                ExecuteMsg::ReceiveNft(token_msg),
+            )
+            .unwrap();
+        };
+        create(deps.as_mut(), "alice", "cw721_contract_addr", TEST_DENOM);
+        create(deps.as_mut(), "bob", "cw721_other_addr", "uatom");
+        create(deps.as_mut(), "alice", "cw721_other_addr", TEST_DENOM);
+        create(deps.as_mut(), "alice", "cw721_contract_addr", "uatom");
+
+        let ids = |msg: QueryMsg| -> Vec<u64> {
+            let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+            from_binary::<Vec<response::Auction>>(&res)
+                .unwrap()
+                .into_iter()
+                .map(|auction| auction.id)
+                .collect()
+        };
+
+        assert_eq!(
+            ids(QueryMsg::AuctionsBySeller {
+                seller: "alice".to_string(),
+                start_after: None,
+                limit: None,
+            }),
+            vec![3, 2, 0]
+        );
+        assert_eq!(
+            ids(QueryMsg::AuctionsBySeller {
+                seller: "alice".to_string(),
+                start_after: Some(3),
+                limit: Some(1),
+            }),
+            vec![2]
+        );
+        assert_eq!(
+            ids(QueryMsg::AuctionsByCollection {
+                collection: "cw721_other_addr".to_string(),
+                start_after: None,
+                limit: None,
+            }),
+            vec![2, 1]
+        );
+        assert_eq!(
+            ids(QueryMsg::AuctionsByPayment {
+                payment: "uatom".to_string(),
+                start_after: Some(3),
+                limit: None,
+            }),
+            vec![1]
+        );
+        assert_eq!(
+            ids(QueryMsg::AuctionList {
+                status: None,
+                settled: None,
+                start_after: Some(1),
+                limit: Some(3),
+            }),
+            vec![0]
+        );
+        assert_eq!(
+            ids(QueryMsg::AuctionList {
+                status: Some(AuctionStatus::OpeningPeriod),
+                settled: None,
+                start_after: Some(3),
+                limit: None,
+            }),
+            vec![2, 1, 0]
+        );
+        assert_eq!(
+            ids(QueryMsg::AuctionList {
+                status: None,
+                settled: Some(true),
+                start_after: None,
+                limit: None,
+            }),
+            Vec::<u64>::new()
+        );
+    }
+
+    #[test]
+    fn test_bids_by_bidder() {
+        let mut deps = mock_dependencies();
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        for _ in 0..2 {
+            let post_auction_msg = ExecuteMsg::Auction {
+                name: "test_bids_by_bidder".to_string(),
+                start_timestamp: 1571797500,
+                duration: 3600,
+                payment_type: PaymentType::Coin,
+                payment: TEST_DENOM.to_string(),
+                min_price: None,
+                mode: None,
+                accepted_payments: None,
+                lots_expected: None,
+                eligibility: None,
+                metadata: None,
+            };
+            let info = mock_info("alice", &coins(1000, "uatom"));
+            execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+        }
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797600);
+        for (bidder, auction_id, amount) in [
+            ("bob", 0, 10),
+            ("carol", 0, 20),
+            ("bob", 0, 30),
+            ("bob", 1, 10),
+            ("carol", 1, 20),
+        ] {
+            let bid = ExecuteMsg::BidForDenom {
+                bidder: None,
+                auction_id,
+                proof: None,
+            };
+            let info = mock_info(bidder, &coins(amount, TEST_DENOM));
+            execute(deps.as_mut(), env.clone(), info, bid).unwrap();
+        }
+
+        let msg = QueryMsg::BidsByBidder {
+            address: "bob".to_string(),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let bids: Vec<response::BidderAuction> = from_binary(&res).unwrap();
+        let asset = PaymentAsset {
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+        };
+        assert_eq!(bids.len(), 2);
+        assert_eq!(bids[0].auction_id, 1);
+        assert!(!bids[0].leading);
+        assert_eq!(bids[0].locked, vec![(asset.clone(), Uint128::new(10))]);
+
+        assert_eq!(bids[1].auction_id, 0);
+        assert_eq!(bids[1].bid_num, 2);
+        assert_eq!(bids[1].highest_bid.as_ref().unwrap().value, 30);
+        assert!(bids[1].leading);
+        // any bid before the candle end may win
+        assert_eq!(bids[1].locked, vec![(asset, Uint128::new(40))]);
+
+        let msg = QueryMsg::BidsByBidder {
+            address: "carol".to_string(),
+            start_after: Some(1),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env, msg).unwrap();
+        let bids: Vec<response::BidderAuction> = from_binary(&res).unwrap();
+        assert_eq!(bids.len(), 1);
+        assert_eq!(bids[0].auction_id, 0);
+        assert!(!bids[0].leading);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        // alice sells twice, bob's auction has no lots
+        for (seller, lots) in [("alice", 1000), ("alice", 1000), ("bob", 0)] {
+            let post_auction_msg = ExecuteMsg::Auction {
+                name: "test_stats".to_string(),
+                start_timestamp: 1571797500,
+                duration: 3600,
+                payment_type: PaymentType::Coin,
+                payment: TEST_DENOM.to_string(),
+                min_price: None,
+                mode: None,
+                accepted_payments: None,
+                lots_expected: None,
+                eligibility: None,
+                metadata: None,
+            };
+            let info = mock_info(seller, &coins(lots, "uatom"));
+            execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+        }
+
+        // bid before the candle end
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797550);
+        for (bidder, amount) in [("bob", 10), ("carol", 20), ("bob", 30)] {
+            let bid = ExecuteMsg::BidForDenom {
+                bidder: None,
+                auction_id: 0,
+                proof: None,
+            };
+            let info = mock_info(bidder, &coins(amount, TEST_DENOM));
+            execute(deps.as_mut(), env.clone(), info, bid).unwrap();
+        }
+        let flow = ExecuteMsg::FlowRefund { auction_id: 2 };
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), flow).unwrap();
+
+        env.block.time = Timestamp::from_seconds(1571797500 + 3600 + 1);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("anyone", &[]),
+            blow_candle,
+        )
+        .unwrap();
+        let flow = ExecuteMsg::FlowRefund { auction_id: 1 };
+        execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), flow).unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Stats {}).unwrap();
+        let stats: response::Stats = from_binary(&res).unwrap();
+        assert_eq!(stats.stats.created, 3);
+        assert_eq!(stats.stats.settled, 1);
+        assert_eq!(stats.stats.passed, 1);
+        assert_eq!(stats.stats.cancelled, 1);
+        assert_eq!(stats.stats.bids, 3);
+        assert_eq!(stats.average_bids, Decimal::one());
+        assert_eq!(stats.stats.volume.len(), 1);
+        assert_eq!(
+            stats.top_sellers,
+            vec![(Addr::unchecked("alice"), 1), (Addr::unchecked("bob"), 0)]
+        );
+
+        let msg = QueryMsg::SellerStats {
+            address: "alice".to_string(),
+        };
+        let res = query(deps.as_ref(), env, msg).unwrap();
+        let stats: response::SellerStats = from_binary(&res).unwrap();
+        assert_eq!(stats.stats.created, 2);
+        assert_eq!(stats.stats.settled, 1);
+        assert_eq!(stats.stats.passed, 1);
+        assert_eq!(stats.stats.bids, 3);
+    }
+
+    #[test]
+    fn test_migrate() {
+        use crate::contract::migrate;
+        use crate::migration::v0_1;
+        use cosmwasm_std::Api;
+        use cw2::{get_contract_version, set_contract_version};
+
+        let mut deps = mock_dependencies();
+        let migrate_msg = MigrateMsg {
+            min_auction_duration: None,
+            max_auction_duration: None,
+            enable_auction: None,
+            fee_rate: Some(3),
+            default_denom: None,
+            max_royalty: None,
+        };
+
+        // 0.1 fixture state
+        let seller = deps.api.addr_canonicalize("alice").unwrap();
+        let config = v0_1::Config {
+            auction_num: 2,
+            min_auction_duration: 0,
+            max_auction_duration: 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_contract_addr".to_string()],
+            version: v0_1::ContractVersion {
+                contract: "crates.io:candle_auction".to_string(),
+                version: "0.1.0".to_string(),
+            },
+            owner: deps.api.addr_canonicalize("admin").unwrap(),
+            oracle_contract: deps.api.addr_canonicalize("oracle_contract").unwrap(),
+        };
+        v0_1::CONFIG.save(deps.as_mut().storage, &config).unwrap();
+        let auction = v0_1::Auction {
+            name: "test_migrate".to_string(),
+            start_timestmap: 1571797400,
+            auction_duration: 3600,
+            bidders: vec![
+                ("bob".to_string(), 1571797410, 10),
+                ("carol".to_string(), 1571797420, 20),
+            ],
+            curr_winner: Some(("carol".to_string(), 1571797420, 20)),
+            tokens: vec![("cw721_contract_addr".to_string(), "token_1".to_string())],
+            seller: seller.clone(),
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            bid_num: 2,
+            is_candle_blow: true,
+        };
+        v0_1::AUCTIONS
+            .save(deps.as_mut().storage, 0, &auction)
+            .unwrap();
+        let auction = v0_1::Auction {
+            bidders: vec![],
+            curr_winner: None,
+            bid_num: 0,
+            is_candle_blow: false,
+            ..auction
+        };
+        v0_1::AUCTIONS
+            .save(deps.as_mut().storage, 1, &auction)
+            .unwrap();
+
+        // "0.10.0" is newer than the current version
+        set_contract_version(deps.as_mut().storage, "crates.io:candle_auction", "0.10.0").unwrap();
+        migrate(deps.as_mut(), mock_env(), migrate_msg.clone()).unwrap_err();
+
+        set_contract_version(deps.as_mut().storage, "crates.io:candle_auction", "0.1.0").unwrap();
+        let res = migrate(deps.as_mut(), mock_env(), migrate_msg.clone()).unwrap();
+        assert!(res.attributes.contains(&Attribute {
+            key: "steps".to_string(),
+            value: "0.2.0".to_string(),
+        }));
+        let version = get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: response::Config = from_binary(&res).unwrap();
+        assert_eq!(config.auction_num, 2);
+        assert_eq!(config.fee_rate, 3);
+        assert_eq!(config.owner, Addr::unchecked("admin"));
+
+        let msg = QueryMsg::Collections {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let collections: Vec<response::Collection> = from_binary(&res).unwrap();
+        assert_eq!(collections[0].contract, "cw721_contract_addr");
+
+        // the auctions are indexed in the new layout
+        let msg = QueryMsg::AuctionsByCollection {
+            collection: "cw721_contract_addr".to_string(),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let auctions: Vec<response::Auction> = from_binary(&res).unwrap();
+        assert_eq!(auctions.len(), 2);
+        assert_eq!(auctions[1].id, 0);
+        assert_eq!(
+            auctions[1].lots,
+            vec![Lot::Cw721 {
+                contract: "cw721_contract_addr".to_string(),
+                token_id: "token_1".to_string(),
+            }]
+        );
+        assert_eq!(
+            auctions[1].winners,
+            vec![("carol".to_string(), 1571797420, 20)]
+        );
+
+        let msg = QueryMsg::BidsByBidder {
+            address: "bob".to_string(),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let bids: Vec<response::BidderAuction> = from_binary(&res).unwrap();
+        assert_eq!(bids.len(), 1);
+        assert!(!bids[0].leading);
+
+        let msg = QueryMsg::SellerStats {
+            address: "alice".to_string(),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let stats: response::SellerStats = from_binary(&res).unwrap();
+        assert_eq!(stats.stats.created, 2);
+        assert_eq!(stats.stats.settled, 1);
+        assert_eq!(stats.stats.bids, 2);
+
+        // Migrating again runs no step
+        let res = migrate(deps.as_mut(), mock_env(), migrate_msg).unwrap();
+        assert!(res.attributes.contains(&Attribute {
+            key: "steps".to_string(),
+            value: "".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_pause() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        let owner = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+        let grant = ExecuteMsg::GrantRole {
+            address: "guardian".to_string(),
+            role: Role::Guardian,
+        };
+        execute(deps.as_mut(), mock_env(), owner.clone(), grant).unwrap();
+
+        let post_auction_msg = || ExecuteMsg::Auction {
+            name: "test_pause".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg()).unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("alice", &[]),
+            post_auction_msg(),
+        )
+        .unwrap();
+
+        // Only the owner and the guardian can pause
+        let pause = ExecuteMsg::Pause {
+            scopes: vec![PauseScope::Creation, PauseScope::Bidding],
+            reason: "incident".to_string(),
+        };
+        let info = mock_info("bob", &[]);
+        execute(deps.as_mut(), mock_env(), info, pause.clone()).unwrap_err();
+        let guardian = mock_info("guardian", &[]);
+        execute(deps.as_mut(), mock_env(), guardian.clone(), pause).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseState {}).unwrap();
+        let state: response::PauseState = from_binary(&res).unwrap();
+        assert_eq!(state.paused.len(), 2);
+        assert_eq!(state.paused[0].paused_by, "guardian");
+
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        let err = execute(deps.as_mut(), mock_env(), info, post_auction_msg()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                scope: "creation".to_string(),
+                reason: "incident".to_string(),
+            }
+        );
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797600);
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(10, TEST_DENOM));
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), bid.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                scope: "bidding".to_string(),
+                reason: "incident".to_string(),
+            }
+        );
+
+        // Lots are always returned
+        let flow = ExecuteMsg::FlowRefund { auction_id: 1 };
+        execute(deps.as_mut(), env.clone(), mock_info("alice", &[]), flow).unwrap();
+
+        // Only the owner resumes
+        let unpause = ExecuteMsg::Unpause {
+            scopes: vec![PauseScope::Bidding],
+        };
+        execute(deps.as_mut(), mock_env(), guardian.clone(), unpause.clone()).unwrap_err();
+        execute(deps.as_mut(), mock_env(), owner.clone(), unpause).unwrap();
+        let mut early = mock_env();
+        early.block.time = Timestamp::from_seconds(1571797550);
+        let carol = mock_info("carol", &coins(5, TEST_DENOM));
+        execute(deps.as_mut(), early, carol, bid.clone()).unwrap();
+        execute(deps.as_mut(), env, info, bid).unwrap();
+
+        // The non-winners claim their refunds while the settlement is paused
+        let pause = |scopes: Vec<PauseScope>| ExecuteMsg::Pause {
+            scopes,
+            reason: "incident".to_string(),
+        };
+        let scopes = vec![PauseScope::Settlement, PauseScope::Refunds];
+        execute(deps.as_mut(), mock_env(), guardian, pause(scopes)).unwrap();
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797500 + 3601);
+        let claim = ExecuteMsg::ClaimRefund { auction_id: 0 };
+        let anyone = mock_info("dave", &[]);
+        let err = execute(deps.as_mut(), env.clone(), anyone.clone(), claim.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                scope: "refunds".to_string(),
+                reason: "incident".to_string(),
+            }
+        );
+        let unpause = |scopes: Vec<PauseScope>| ExecuteMsg::Unpause { scopes };
+        let info = owner.clone();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            unpause(vec![PauseScope::Refunds]),
+        )
+        .unwrap();
+
+        // bob bid after the candle end
+        let res = execute(deps.as_mut(), env.clone(), anyone.clone(), claim.clone()).unwrap();
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert_eq!(
+            msgs,
+            vec![BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(10, TEST_DENOM),
+            }
+            .into()]
+        );
+        let res = execute(deps.as_mut(), env.clone(), anyone.clone(), claim).unwrap();
+        assert!(res.messages.is_empty());
+        let msg = QueryMsg::BidsByBidder {
+            address: "bob".to_string(),
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), msg).unwrap();
+        let bids: Vec<response::BidderAuction> = from_binary(&res).unwrap();
+        assert!(!bids[0].leading);
+        assert!(bids[0].locked.is_empty());
+
+        // and the settlement does not refund them twice
+        let blow = ExecuteMsg::BlowCandle { auction_id: 0 };
+        let err = execute(deps.as_mut(), env.clone(), anyone.clone(), blow.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                scope: "settlement".to_string(),
+                reason: "incident".to_string(),
+            }
+        );
+        let info = owner;
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            unpause(vec![PauseScope::Settlement]),
+        )
+        .unwrap();
+        let res = execute(deps.as_mut(), env, anyone, blow).unwrap();
+        assert!(!res.messages.iter().any(|msg| matches!(
+            &msg.msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address, .. }) if to_address == "bob"
+        )));
+    }
+
+    #[test]
+    fn test_roles() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        let owner = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+        // Only the owner grants roles
+        let grant = |address: &str, role: Role| ExecuteMsg::GrantRole {
+            address: address.to_string(),
+            role,
+        };
+        let info = mock_info("operator", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            grant("operator", Role::Operator),
+        )
+        .unwrap_err();
+        for (address, role) in [
+            ("operator", Role::Operator),
+            ("fee_manager", Role::FeeManager),
+            ("fee_manager", Role::Guardian),
+        ] {
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                owner.clone(),
+                grant(address, role),
+            )
+            .unwrap();
+        }
+
+        let msg = QueryMsg::Roles {
+            address: "fee_manager".to_string(),
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let roles: response::Roles = from_binary(&res).unwrap();
+        assert!(!roles.owner);
+        assert_eq!(roles.roles, vec![Role::FeeManager, Role::Guardian]);
+
+        // The operator manages the allowlists
+        let add_collection = ExecuteMsg::AddCollection {
+            contract: "cw721_contract_addr".to_string(),
+            royalty: None,
+            min_duration: None,
+        };
+        let info = mock_info("fee_manager", &[]);
+        execute(deps.as_mut(), mock_env(), info, add_collection.clone()).unwrap_err();
+        let info = mock_info("operator", &[]);
+        execute(deps.as_mut(), mock_env(), info, add_collection).unwrap();
+
+        // The fee manager updates the fee rate and withdraws the fees
+        let update_fee_rate = ExecuteMsg::UpdateFeeRate { fee_rate: 5 };
+        let info = mock_info("operator", &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, update_fee_rate.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::MissingRole {
+                sender: "operator".to_string(),
+                role: "fee_manager".to_string()
+            }
+        );
+        let fee_manager = mock_info("fee_manager", &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            fee_manager.clone(),
+            update_fee_rate,
+        )
+        .unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_roles".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797550);
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(1000, TEST_DENOM));
+        execute(deps.as_mut(), env.clone(), info, bid).unwrap();
+        env.block.time = Timestamp::from_seconds(1571797500 + 3600 + 1);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        execute(deps.as_mut(), env, mock_info("anyone", &[]), blow_candle).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Fees {}).unwrap();
+        let fees: Vec<(PaymentAsset, Uint128)> = from_binary(&res).unwrap();
+        assert_eq!(fees[0].1, Uint128::new(50));
+
+        let withdraw = |amount: u128| ExecuteMsg::WithdrawFees {
+            payment: TEST_DENOM.to_string(),
+            amount: Some(Uint128::new(amount)),
+            recipient: Some("treasury".to_string()),
+        };
+        let info = mock_info("operator", &[]);
+        execute(deps.as_mut(), mock_env(), info, withdraw(30)).unwrap_err();
+        let res = execute(deps.as_mut(), mock_env(), fee_manager.clone(), withdraw(30)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury".to_string(),
+                amount: coins(30, TEST_DENOM),
+            })
+        );
+        let err = execute(deps.as_mut(), mock_env(), fee_manager, withdraw(30)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientFund {
+                amount: Uint128::new(20),
+                required: Uint128::new(30),
+            }
+        );
+
+        // Revoked roles are lost
+        let revoke = ExecuteMsg::RevokeRole {
+            address: "fee_manager".to_string(),
+            role: Role::Guardian,
+        };
+        execute(deps.as_mut(), mock_env(), owner, revoke).unwrap();
+        let pause = ExecuteMsg::Pause {
+            scopes: vec![PauseScope::Bidding],
+            reason: "incident".to_string(),
+        };
+        let info = mock_info("fee_manager", &[]);
+        execute(deps.as_mut(), mock_env(), info, pause).unwrap_err();
+    }
+
+    #[test]
+    fn test_creation_policy() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        let owner = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_creation_policy".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let create = |deps: DepsMut, seller: &str| {
+            execute(
+                deps,
+                mock_env(),
+                mock_info(seller, &[]),
+                post_auction_msg.clone(),
+            )
+        };
+        create(deps.as_mut(), "bob").unwrap();
+
+        // Allowlisted sellers only
+        let policy = ExecuteMsg::UpdateCreationPolicy {
+            policy: CreationPolicy::Allowlist,
+        };
+        execute(deps.as_mut(), mock_env(), owner.clone(), policy).unwrap();
+        let add_sellers = ExecuteMsg::AddSellers {
+            sellers: vec!["carol".to_string()],
+        };
+        execute(deps.as_mut(), mock_env(), owner.clone(), add_sellers).unwrap();
+
+        let err = create(deps.as_mut(), "bob").unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::SellerNotAllowed {
+                seller: "bob".to_string()
+            }
+        );
+        create(deps.as_mut(), "carol").unwrap();
+
+        let msg = QueryMsg::Sellers {
+            start_after: None,
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), msg).unwrap();
+        let sellers: Vec<String> = from_binary(&res).unwrap();
+        assert_eq!(sellers, vec!["carol".to_string()]);
+
+        // Holders of the collection only
+        let policy = ExecuteMsg::UpdateCreationPolicy {
+            policy: CreationPolicy::NftHolders {
+                collection: "cw721_contract_addr".to_string(),
+            },
+        };
+        execute(deps.as_mut(), mock_env(), owner, policy).unwrap();
+        create(deps.as_mut(), "carol").unwrap_err();
+        create(deps.as_mut(), "alice").unwrap();
+    }
+
+    #[test]
+    fn test_bidder_eligibility() {
+        use tiny_keccak::{Hasher, Keccak};
+
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let keccak = |data: &[u8]| {
+            let mut hasher = Keccak::v256();
+            let mut hash = [0u8; 32];
+            hasher.update(data);
+            hasher.finalize(&mut hash);
+            hash
+        };
+        let (bob, carol) = (keccak(b"bob"), keccak(b"carol"));
+        let root = match bob <= carol {
+            true => keccak(&[bob, carol].concat()),
+            false => keccak(&[carol, bob].concat()),
+        };
+
+        let rules = [
+            BidderEligibility::Allowlist {
+                addresses: vec!["bob".to_string()],
+            },
+            BidderEligibility::Cw20Holders {
+                token: "cw20_contract_addr".to_string(),
+                min_balance: Uint128::new(50),
+            },
+            BidderEligibility::MerkleRoot {
+                root: hex::encode(root),
+            },
+        ];
+        for eligibility in rules {
+            let post_auction_msg = ExecuteMsg::Auction {
+                name: "test_bidder_eligibility".to_string(),
+                start_timestamp: 1571797500,
+                duration: 3600,
+                payment_type: PaymentType::Coin,
+                payment: TEST_DENOM.to_string(),
+                min_price: None,
+                mode: None,
+                accepted_payments: None,
+                lots_expected: None,
+                eligibility: Some(eligibility),
+                metadata: None,
+            };
+            let info = mock_info("alice", &coins(1000, "uatom"));
+            execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+        }
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797550);
+        let bid = |deps: DepsMut, bidder: &str, auction_id: u64, proof: Option<Vec<String>>| {
+            let msg = ExecuteMsg::BidForDenom {
+                bidder: None,
+                auction_id,
+                proof,
+            };
+            execute(
+                deps,
+                env.clone(),
+                mock_info(bidder, &coins(10, TEST_DENOM)),
+                msg,
+            )
+        };
+
+        for auction_id in 0..2 {
+            let err = bid(deps.as_mut(), "dave", auction_id, None).unwrap_err();
+            assert_eq!(
+                err,
+                ContractError::NotEligible {
+                    bidder: "dave".to_string()
+                }
+            );
+            bid(deps.as_mut(), "bob", auction_id, None).unwrap();
+        }
+
+        // Merkle proof of `bob` is the `carol` leaf
+        let proof = Some(vec![hex::encode(carol)]);
+        bid(deps.as_mut(), "dave", 2, proof.clone()).unwrap_err();
+        bid(deps.as_mut(), "bob", 2, None).unwrap_err();
+        bid(deps.as_mut(), "bob", 2, proof).unwrap();
+
+        // Invalid root is rejected at creation
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_bidder_eligibility".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: Some(BidderEligibility::MerkleRoot {
+                root: "abcd".to_string(),
+            }),
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap_err();
+    }
+
+    #[test]
+    fn test_bid_on_behalf() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_bid_on_behalf".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797550);
+        let bid = |bidder: &str| ExecuteMsg::BidForDenom {
+            bidder: Some(bidder.to_string()),
+            auction_id: 0,
+            proof: None,
+        };
+
+        // Invalid beneficiaries are rejected
+        let info = mock_info("bob", &coins(10, TEST_DENOM));
+        execute(deps.as_mut(), env.clone(), info.clone(), bid("Carol")).unwrap_err();
+
+        execute(deps.as_mut(), env.clone(), info, bid("carol")).unwrap();
+        let info = mock_info("bob", &coins(30, TEST_DENOM));
+        execute(deps.as_mut(), env.clone(), info, bid("carol")).unwrap();
+
+        let res = query(deps.as_ref(), env, QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: Option<response::Auction> = from_binary(&res).unwrap();
+        let top_bid = auction.unwrap().top_bid.unwrap();
+        assert_eq!(top_bid.bidder, Addr::unchecked("carol"));
+        assert_eq!(top_bid.payer(), &Addr::unchecked("bob"));
+
+        // The payer gets the refund, the beneficiary the lots
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797500 + 3601);
+        let blow_candle = ExecuteMsg::BlowCandle { auction_id: 0 };
+        let info = mock_info("anyone", &[]);
+        let res = execute(deps.as_mut(), end_env, info, blow_candle).unwrap();
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        assert!(msgs.contains(
+            &BankMsg::Send {
+                to_address: "bob".to_string(),
+                amount: coins(10, TEST_DENOM),
+            }
+            .into()
+        ));
+        assert!(msgs.contains(
+            &BankMsg::Send {
+                to_address: "carol".to_string(),
+                amount: coins(1000, "uatom"),
+            }
+            .into()
+        ));
+    }
+
+    #[test]
+    fn test_update_auction() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 600,
+            max_auction_duration: 2 * 24 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_update_auction".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        let update = |duration: u64| ExecuteMsg::UpdateAuction {
+            auction_id: 0,
+            name: Some("renamed".to_string()),
+            start_timestamp: Some(1571798000),
+            duration: Some(duration),
+            min_price: Some(100),
+            metadata: None,
+        };
+
+        // Only the seller edits
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("bob", &[]),
+            update(7200),
+        );
+        assert_eq!(
+            err.unwrap_err(),
+            ContractError::NotOwner {
+                sender: "bob".to_string(),
+                owner: "alice".to_string()
+            }
+        );
+
+        // The duration stays within the config bounds
+        let info = mock_info("alice", &[]);
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), update(60)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidDuration {
+                duration: 60,
+                min: 600,
+                max: 2 * 24 * 3600
+            }
+        );
+
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), update(7200)).unwrap();
+        assert_eq!(res.events[0].ty, "auction_updated");
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert_eq!(auction.name, "renamed");
+        assert_eq!(auction.start_timestamp, 1571798000);
+        assert_eq!(auction.auction_duration, 7200);
+        assert_eq!(auction.min_price, Some(100));
+
+        // A new duration still satisfies the collections of the deposited lots
+        let add_collection = ExecuteMsg::AddCollection {
+            contract: "cw721_contract_b".to_string(),
+            royalty: None,
+            min_duration: Some(7200),
+        };
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin", &[]),
+            add_collection,
+        )
+        .unwrap();
+        let deposit = ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender: "alice".to_string(),
+            token_id: "b1".to_string(),
+            msg: to_binary(&Auction {
+                id: 0,
+                bidder: None,
+                proof: None,
+            })
+            .unwrap(),
+        });
+        let collection_b = mock_info("cw721_contract_b", &[]);
+        execute(deps.as_mut(), mock_env(), collection_b, deposit).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), update(3600)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DurationTooShort {
+                input_duration: 3600,
+                min_duration: 7200
+            }
+        );
+
+        // Leaving the duration unchanged skips its validation
+        let rename = ExecuteMsg::UpdateAuction {
+            auction_id: 0,
+            name: Some("renamed again".to_string()),
+            start_timestamp: None,
+            duration: None,
+            min_price: None,
+            metadata: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), rename).unwrap();
+
+        // Locked once started
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571798100);
+        let err = execute(deps.as_mut(), env, info, update(7200)).unwrap_err();
+        assert_eq!(err, ContractError::AuctionLocked {});
+    }
+
+    #[test]
+    fn test_auction_metadata() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec!["cw721_contract_addr".to_string()],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let metadata = AuctionMetadata {
+            description: Some("A candle auction".to_string()),
+            image: Some("ipfs://image".to_string()),
+            tags: vec!["art".to_string()],
+            external_url: Some("https://example.com".to_string()),
+        };
+        let create_msg = |name: String, metadata: AuctionMetadata| Cw721HookMsg::CreateAuction {
+            name,
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: None,
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: Some(metadata),
+        };
+        let receive = |msg: Cw721HookMsg| {
+            ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+                sender: "alice".to_string(),
+                token_id: "test_token".to_string(),
+                msg: to_binary(&msg).unwrap(),
+            })
+        };
+        let info = mock_info("cw721_contract_addr", &[]);
+
+        // Lengths are counted in graphemes
+        let name = "🕯️".repeat(64);
+        let msg = receive(create_msg(name.clone(), metadata.clone()));
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert_eq!(auction.name, name);
+        assert_eq!(auction.metadata, metadata);
+        assert_eq!(auction.lot_metadata.len(), 1);
+        assert_eq!(
+            auction.lot_metadata[0].token_uri,
+            Some("ipfs://test_token".to_string())
+        );
+
+        let long = AuctionMetadata {
+            description: Some("a".repeat(1025)),
+            ..metadata
+        };
+        let msg = receive(create_msg("test_auction_metadata".to_string(), long));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidMetadata {
+                field: "description".to_string(),
+                max: 1024
+            }
+        );
+    }
+
+    #[test]
+    fn test_relist() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+        let post_auction_msg = ExecuteMsg::Auction {
+            name: "test_relist".to_string(),
+            start_timestamp: 1571797500,
+            duration: 3600,
+            payment_type: PaymentType::Coin,
+            payment: TEST_DENOM.to_string(),
+            min_price: Some(10),
+            mode: None,
+            accepted_payments: None,
+            lots_expected: None,
+            eligibility: None,
+            metadata: None,
+        };
+        let info = mock_info("alice", &coins(1000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+
+        // Nobody bid
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797500 + 3601);
+        let relist = ExecuteMsg::Relist {
+            auction_id: 0,
+            start_timestamp: 1571801200,
+            duration: 7200,
+            min_price: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("bob", &[]),
+            relist.clone(),
+        );
+        assert_eq!(
+            err.unwrap_err(),
+            ContractError::NotOwner {
+                sender: "bob".to_string(),
+                owner: "alice".to_string()
+            }
+        );
+        // The payment is checked against the current restrictions
+        let restrict = |restrict_payments: bool| ExecuteMsg::UpdateConfig {
+            min_auction_duration: None,
+            max_auction_duration: None,
+            enable_auction: None,
+            fee_rate: None,
+            default_denom: None,
+            price_oracle: None,
+            restrict_payments: Some(restrict_payments),
+            max_royalty: None,
+            keeper_reward: None,
+        };
+        let owner = mock_info("admin", &[]);
+        execute(deps.as_mut(), env.clone(), owner.clone(), restrict(true)).unwrap();
+        let info = mock_info("alice", &[]);
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), relist.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedPayment {
+                payment: TEST_DENOM.to_string()
+            }
+        );
+        execute(deps.as_mut(), env.clone(), owner, restrict(false)).unwrap();
+
+        // A refund claim on the passed auction does not leak into the new one
+        let claim = ExecuteMsg::ClaimRefund { auction_id: 0 };
+        execute(deps.as_mut(), env.clone(), mock_info("bob", &[]), claim).unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert_eq!(auction.candle_end_time, None);
+
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), relist.clone()).unwrap();
+        assert!(res.messages.is_empty());
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Auction { id: 1 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert_eq!(auction.start_timestamp, 1571801200);
+        assert_eq!(auction.auction_duration, 7200);
+        assert_eq!(auction.min_price, Some(10));
+        assert_eq!(auction.candle_end_time, None);
+        assert_eq!(
+            auction.lots,
+            vec![Lot::Native {
+                denom: "uatom".to_string(),
+                amount: Uint128::new(1000),
+            }]
+        );
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Auction { id: 0 }).unwrap();
+        let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+        assert!(auction.settled);
+        assert!(auction.lots.is_empty());
+
+        // The lots moved once
+        let err = execute(deps.as_mut(), env.clone(), info, relist).unwrap_err();
+        assert_eq!(err, ContractError::AuctionNotPassed {});
+        let res = query(deps.as_ref(), env, QueryMsg::Stats {}).unwrap();
+        let stats: response::Stats = from_binary(&res).unwrap();
+        assert_eq!(stats.stats.created, 2);
+        assert_eq!(stats.stats.passed, 1);
+    }
+
+    #[test]
+    fn test_settle_batch() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(mock::mock_query_handle);
+
+        let msg = InstantiateMsg {
+            min_auction_duration: 0,
+            max_auction_duration: 2 * 24 * 30 * 3600,
+            enable_auction: true,
+            fee_rate: 2,
+            default_denom: TEST_DENOM.to_string(),
+            support_contract: vec![],
+            oracle_contract: "oracle_contract".to_string(),
+            price_oracle: None,
+            payment_allowlist: None,
+            max_royalty: None,
+        };
+        let owner = mock_info("admin", &[]);
+        instantiate(deps.as_mut(), mock_env(), owner.clone(), msg).unwrap();
+        let update_config = ExecuteMsg::UpdateConfig {
+            min_auction_duration: None,
+            max_auction_duration: None,
+            enable_auction: None,
+            fee_rate: None,
+            default_denom: None,
+            price_oracle: None,
+            restrict_payments: None,
+            max_royalty: None,
+            keeper_reward: Some(50),
+        };
+        execute(deps.as_mut(), mock_env(), owner, update_config).unwrap();
+
+        // the last auction is still running at the batch
+        for duration in [3600, 3600, 7200] {
+            let post_auction_msg = ExecuteMsg::Auction {
+                name: "test_settle_batch".to_string(),
+                start_timestamp: 1571797500,
+                duration,
+                payment_type: PaymentType::Coin,
+                payment: TEST_DENOM.to_string(),
+                min_price: None,
+                mode: None,
+                accepted_payments: None,
+                lots_expected: None,
+                eligibility: None,
+                metadata: None,
+            };
+            let info = mock_info("alice", &coins(1000, "uatom"));
+            execute(deps.as_mut(), mock_env(), info, post_auction_msg).unwrap();
+        }
+
+        let mut env = mock_env();
+        env.block.time = Timestamp::from_seconds(1571797550);
+        let bid = ExecuteMsg::BidForDenom {
+            bidder: None,
+            auction_id: 0,
+            proof: None,
+        };
+        let info = mock_info("bob", &coins(100, TEST_DENOM));
+        execute(deps.as_mut(), env, info, bid).unwrap();
+
+        let mut end_env = mock_env();
+        end_env.block.time = Timestamp::from_seconds(1571797500 + 3601);
+        let settle = |start_after: Option<u64>| ExecuteMsg::SettleBatch {
+            start_after,
+            limit: None,
+        };
+        let keeper = mock_info("keeper", &[]);
+        let res = execute(deps.as_mut(), end_env.clone(), keeper.clone(), settle(None)).unwrap();
+        let keeper_settle = |auction_id: u64| ExecuteMsg::KeeperSettle {
+            auction_id,
+            keeper: "keeper".to_string(),
+        };
+        let dispatch = |auction_id: u64| {
+            let msg = WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&keeper_settle(auction_id)).unwrap(),
+                funds: vec![],
+            };
+            SubMsg::reply_on_error(msg, auction_id)
+        };
+        assert_eq!(res.messages, vec![dispatch(0), dispatch(1)]);
+
+        // only the contract settles for a keeper
+        let err = execute(
+            deps.as_mut(),
+            end_env.clone(),
+            keeper.clone(),
+            keeper_settle(0),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized { .. }));
+
+        let contract = mock_info(MOCK_CONTRACT_ADDR, &[]);
+        let res = execute(deps.as_mut(), end_env.clone(), contract, keeper_settle(0)).unwrap();
+        let msgs: Vec<CosmosMsg> = res.messages.into_iter().map(|m| m.msg).collect();
+        // half of the 2% fee
+        assert!(msgs.contains(
+            &BankMsg::Send {
+                to_address: "keeper".to_string(),
+                amount: coins(1, TEST_DENOM),
+            }
+            .into()
+        ));
+        let res = query(deps.as_ref(), end_env.clone(), QueryMsg::Fees {}).unwrap();
+        let fees: Vec<(PaymentAsset, Uint128)> = from_binary(&res).unwrap();
+        assert_eq!(fees[0].1, Uint128::new(1));
+
+        // the second auction fails to settle, e.g. a rejected lot transfer,
+        // its sub-message is reverted and the batch goes on
+        let failed = Reply {
+            id: 1,
+            result: SubMsgResult::Err("transfer rejected".to_string()),
+        };
+        let res = reply(deps.as_mut(), end_env.clone(), failed).unwrap();
+        assert_eq!(res.events[0].ty, "settle_failed");
+
+        for id in 0..3 {
+            let res = query(deps.as_ref(), end_env.clone(), QueryMsg::Auction { id }).unwrap();
+            let auction: response::Auction = from_binary::<Option<_>>(&res).unwrap().unwrap();
+            assert_eq!(auction.settled, id == 0);
+        }
+
+        // keepers move past the failing auction with the cursor
+        let res = execute(deps.as_mut(), end_env.clone(), keeper.clone(), settle(None)).unwrap();
+        assert_eq!(res.messages, vec![dispatch(1)]);
+        let res = execute(deps.as_mut(), end_env, keeper, settle(Some(1))).unwrap();
+        assert!(res.messages.is_empty());
     }
 }